@@ -0,0 +1,32 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use cryptomarket::api::Response;
+
+const ORDERBOOK: &str = r#"{"ch":"orderbook/full","update":{"ETHBTC":{"t":1626866578796,"s":27617207,"a":[["0.060506","0"],["0.060549","12.6431"],["0.060570","0"],["0.060612","0"]],"b":[["0.060439","4.4095"],["0.060414","0"],["0.060407","7.3349"],["0.060390","0"]]}}}"#;
+const TRADES: &str = r#"{"ch":"trades","update":{"BTCCLP":[{"i":1635188320828,"p":"51711712","q":"0.01150","s":"sell","t":1635188320828},{"i":1635188320829,"p":"51711710","q":"0.00200","s":"buy","t":1635188320830}]}}"#;
+const TICKER: &str = r#"{"ch":"ticker/1s","data":{"ETHBTC":{"t":1614815872000,"a":"0.031175","A":"0.03329","b":"0.031148","B":"0.10565","c":"0.031210","o":"0.030781","h":"0.031788","l":"0.030733","v":"62.587","q":"1.951420577","p":"0.000429","P":"1.39","L":1182694927}}}"#;
+
+fn decode(c: &mut Criterion) {
+    let mut group = c.benchmark_group("decode");
+    for (name, msg) in [
+        ("orderbook", ORDERBOOK),
+        ("trades", TRADES),
+        ("ticker", TICKER),
+    ] {
+        group.bench_with_input(BenchmarkId::new("untagged", name), msg, |b, msg| {
+            b.iter(|| serde_json::from_str::<Response>(msg).unwrap())
+        });
+        group.bench_with_input(BenchmarkId::new("decode", name), msg, |b, msg| {
+            b.iter(|| Response::decode(msg).unwrap())
+        });
+        #[cfg(feature = "zero-copy")]
+        if name != "ticker" {
+            group.bench_with_input(BenchmarkId::new("borrowed", name), msg, |b, msg| {
+                b.iter(|| cryptomarket::api::MarketData::decode(msg).unwrap())
+            });
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, decode);
+criterion_main!(benches);
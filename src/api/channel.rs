@@ -0,0 +1,145 @@
+use std::fmt::{self, Display, Formatter};
+
+/// The market data channels of the public websocket API.
+/// ```
+/// use cryptomarket::api::{Channel, OrderbookDepth, OrderbookSpeed};
+///
+/// let channel = Channel::PartialOrderbook(OrderbookDepth::D5, OrderbookSpeed::Ms100);
+/// assert_eq!(channel.to_string(), "orderbook/D5/100ms");
+/// ```
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum Channel {
+    Trades,
+    Ticker(TickerSpeed),
+    PriceTicker(TickerSpeed),
+    /// The tickers of all the symbols subscribed, in a single notification.
+    TickerBatch(TickerSpeed),
+    /// The mini-tickers of all the symbols subscribed, in a single notification.
+    PriceTickerBatch(TickerSpeed),
+    FullOrderbook,
+    PartialOrderbook(OrderbookDepth, OrderbookSpeed),
+    TopOrderbook(OrderbookSpeed),
+    Candles(CandlePeriod),
+}
+
+/// How often ticker notifications are sent.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum TickerSpeed {
+    /// Every second.
+    S1,
+    /// Every three seconds.
+    S3,
+}
+
+/// How often partial and top of book notifications are sent.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum OrderbookSpeed {
+    Ms100,
+    Ms500,
+    Ms1000,
+}
+
+/// The number of price levels of a partial orderbook.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum OrderbookDepth {
+    D5,
+    D10,
+    D20,
+}
+
+/// The period of a candle.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+pub enum CandlePeriod {
+    /// One minute.
+    M1,
+    M3,
+    M15,
+    M30,
+    /// One hour.
+    H1,
+    H4,
+    /// One day.
+    D1,
+    D7,
+    /// One month.
+    Month,
+}
+
+impl Display for Channel {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            | Channel::Trades => write!(f, "trades"),
+            | Channel::Ticker(speed) => write!(f, "ticker/{}", speed),
+            | Channel::PriceTicker(speed) => write!(f, "ticker/price/{}", speed),
+            | Channel::TickerBatch(speed) => write!(f, "ticker/{}/batch", speed),
+            | Channel::PriceTickerBatch(speed) => write!(f, "ticker/price/{}/batch", speed),
+            | Channel::FullOrderbook => write!(f, "orderbook/full"),
+            | Channel::PartialOrderbook(depth, speed) => write!(f, "orderbook/{}/{}", depth, speed),
+            | Channel::TopOrderbook(speed) => write!(f, "orderbook/top/{}", speed),
+            | Channel::Candles(period) => write!(f, "candles/{}", period),
+        }
+    }
+}
+
+impl Display for TickerSpeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            | TickerSpeed::S1 => "1s",
+            | TickerSpeed::S3 => "3s",
+        })
+    }
+}
+
+impl Display for OrderbookSpeed {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            | OrderbookSpeed::Ms100 => "100ms",
+            | OrderbookSpeed::Ms500 => "500ms",
+            | OrderbookSpeed::Ms1000 => "1000ms",
+        })
+    }
+}
+
+impl Display for OrderbookDepth {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            | OrderbookDepth::D5 => "D5",
+            | OrderbookDepth::D10 => "D10",
+            | OrderbookDepth::D20 => "D20",
+        })
+    }
+}
+
+impl Display for CandlePeriod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            | CandlePeriod::M1 => "M1",
+            | CandlePeriod::M3 => "M3",
+            | CandlePeriod::M15 => "M15",
+            | CandlePeriod::M30 => "M30",
+            | CandlePeriod::H1 => "H1",
+            | CandlePeriod::H4 => "H4",
+            | CandlePeriod::D1 => "D1",
+            | CandlePeriod::D7 => "D7",
+            | CandlePeriod::Month => "1M",
+        })
+    }
+}
+
+#[test]
+fn channel_names() {
+    assert_eq!(Channel::Trades.to_string(), "trades");
+    assert_eq!(Channel::Ticker(TickerSpeed::S3).to_string(), "ticker/3s");
+    assert_eq!(
+        Channel::PriceTickerBatch(TickerSpeed::S1).to_string(),
+        "ticker/price/1s/batch"
+    );
+    assert_eq!(
+        Channel::TopOrderbook(OrderbookSpeed::Ms1000).to_string(),
+        "orderbook/top/1000ms"
+    );
+    assert_eq!(
+        Channel::Candles(CandlePeriod::Month).to_string(),
+        "candles/1M"
+    );
+}
@@ -1,11 +1,15 @@
 use crate::prelude::*;
+mod channel;
 mod request;
 mod response;
 mod rest;
+mod symbol;
 
+pub use channel::*;
 pub use request::*;
 pub use response::{Error, *};
 pub use rest::*;
+pub use symbol::*;
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, Hash, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -26,6 +30,57 @@ impl Display for Side {
     }
 }
 
+/// The type of prices, quantities and amounts: `f64` by default, or
+/// `rust_decimal::Decimal` with the `decimal` feature, which keeps the
+/// exact values sent by the exchange.
+#[cfg(not(feature = "decimal"))]
+pub type Number = f64;
+#[cfg(feature = "decimal")]
+pub type Number = rust_decimal::Decimal;
+
+/// The account a value belongs to, either the main account
+/// or one of its sub-accounts.
+#[derive(Debug, Clone, Default, Hash, Eq, PartialEq)]
+pub enum Account {
+    #[default]
+    Main,
+    Sub(String),
+}
+
+impl Display for Account {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            | Account::Main => write!(f, "main"),
+            | Account::Sub(id) => write!(f, "{}", id),
+        }
+    }
+}
+
+// Values returned by the API that are tagged with the account they belong to.
+pub(crate) trait Tagged {
+    fn tagged(self, account: &Account) -> Self;
+}
+
+impl Tagged for Order {
+    fn tagged(mut self, account: &Account) -> Self {
+        self.account = account.clone();
+        self
+    }
+}
+
+impl Tagged for Balance {
+    fn tagged(mut self, account: &Account) -> Self {
+        self.account = account.clone();
+        self
+    }
+}
+
+impl<T: Tagged> Tagged for Vec<T> {
+    fn tagged(self, account: &Account) -> Self {
+        self.into_iter().map(|t| t.tagged(account)).collect()
+    }
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, Hash, Eq, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub enum ReportType {
@@ -106,6 +161,8 @@ pub enum TimeInForce {
 pub struct NewOrder {
     #[builder(default = "crate::api::request::random_id()")]
     pub client_order_id: String,
+    /// The symbol of the order, either a `Symbol` or a string.
+    #[builder(setter(into))]
     pub symbol: String,
     pub side: Side,
     #[builder(default = "Some(OrderType::Limit)")]
@@ -113,11 +170,12 @@ pub struct NewOrder {
     #[builder(default = "None")]
     pub time_in_force: Option<TimeInForce>,
     #[serde_as(as = "DisplayFromStr")]
-    pub quantity: f64,
+    pub quantity: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub price: f64,
+    pub price: Number,
     #[builder(default = "None")]
-    pub stop_price: Option<f64>,
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub stop_price: Option<Number>,
     #[builder(default = "None")]
     pub expire_time: Option<DateTime<Utc>>,
     #[builder(default = "None")]
@@ -142,3 +200,25 @@ impl Display for NewOrder {
 // struct Trade {
 
 // }
+
+#[test]
+fn serialize_new_order() {
+    let order = NewOrderBuilder::default()
+        .symbol("BTCCLP")
+        .side(Side::Buy)
+        .price("51711712.1".parse().unwrap())
+        .quantity("0.00111".parse().unwrap())
+        .build()
+        .unwrap();
+    let json = serde_json::to_value(&order).unwrap();
+    assert_eq!(json["price"], "51711712.1");
+    assert_eq!(json["quantity"], "0.00111");
+}
+
+#[cfg(feature = "decimal")]
+#[test]
+fn exact_increments() {
+    let tick: Number = "0.1".parse().unwrap();
+    let price = tick + tick + tick;
+    assert_eq!(price.to_string(), "0.3");
+}
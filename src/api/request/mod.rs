@@ -4,6 +4,7 @@ use chrono::{DateTime, Utc};
 use crate::api::*;
 #[derive(Serialize, Debug, Clone)]
 #[serde(untagged)]
+#[allow(clippy::enum_variant_names)]
 pub(crate) enum Request<'r> {
     Default {
         method: Method,
@@ -17,10 +18,15 @@ pub(crate) enum Request<'r> {
         id: i64,
         params: Params<'r>,
     },
+
+    Raw {
+        method: &'r str,
+        id: i64,
+        params: serde_json::Value,
+    },
 }
 #[derive(Serialize, Deserialize, Debug, Clone, Hash)]
 #[serde(rename_all = "snake_case")]
-
 pub enum Method {
     Subscribe,
     Unsubscribe,
@@ -48,6 +54,38 @@ pub enum Method {
     Login,
 }
 
+impl std::fmt::Display for Method {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}",
+            match self {
+                | Method::Subscribe => "subscribe",
+                | Method::Unsubscribe => "unsubscribe",
+                | Method::Subscriptions => "subscriptions",
+                | Method::SpotSubscribe => "spot_subscribe",
+                | Method::SpotGetOrders => "spot_get_orders",
+                | Method::SpotNewOrder => "spot_new_order",
+                | Method::SpotUnsubscribe => "spot_unsubscribe",
+                | Method::SpotReplaceOrder => "spot_replace_order",
+                | Method::SpotCancelOrders => "spot_cancel_orders",
+                | Method::SpotCancelOrder => "spot_cancel_order",
+                | Method::SpotBalances => "spot_balances",
+                | Method::SpotFees => "spot_fees",
+                | Method::SpotFee => "spot_fee",
+                | Method::SubscribeTransactions => "subscribe_transactions",
+                | Method::UnsubscribeTransactions => "unsubscribe_transactions",
+                | Method::SubscribeWalletBalances => "subscribe_wallet_balances",
+                | Method::UnsubscribeWalletBalances => "unsubscribe_wallet_balances",
+                | Method::WalletBalances => "wallet_balances",
+                | Method::WalletBalance => "wallet_balance",
+                | Method::GetTransactions => "get_transactions",
+                | Method::Login => "login",
+            }
+        )
+    }
+}
+
 #[serde_as]
 #[derive(Serialize, Debug, Clone)]
 #[serde(untagged)]
@@ -56,6 +94,8 @@ pub enum Params<'r> {
         r#type: &'static str,
         api_key: &'r str,
         timestamp: i64,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        window: Option<u32>,
         signature: String,
     },
     Empty {},
@@ -64,10 +104,10 @@ pub enum Params<'r> {
         symbol: &'r str,
     },
     Symbols {
-        symbols: &'r [&'r str],
+        symbols: Vec<&'r str>,
     },
     SymbolsLimit {
-        symbols: &'r [&'r str],
+        symbols: Vec<&'r str>,
         limit: i32,
     },
     WalletBalance {
@@ -81,9 +121,9 @@ pub enum Params<'r> {
         client_order_id: &'r str,
         new_client_order_id: String,
         #[serde_as(as = "DisplayFromStr")]
-        quantity: f64,
+        quantity: Number,
         #[serde_as(as = "DisplayFromStr")]
-        price: f64,
+        price: Number,
     },
     GetTransactions {
         from: DateTime<Utc>,
@@ -115,29 +155,69 @@ pub enum Params<'r> {
         /// Default value: `ﬁDESC`
         sort: &'r str,
     },
+    Raw(serde_json::Value),
 }
 
 use Params::*;
+// the params, rather than the identifiers of `api::symbol`.
+use Params::{Symbol, Symbols};
 
 impl<'r> Request<'r> {
     pub fn id(&self) -> i64 {
         match self {
             | Self::Default { id, .. } => *id,
             | Self::ChannelRequest { id, .. } => *id,
+            | Self::Raw { id, .. } => *id,
+        }
+    }
+
+    /// The `client_order_id` of the order the request operates on, if any.
+    pub fn client_order_id(&self) -> Option<&str> {
+        match self {
+            | Self::Default { params, .. } => match params {
+                | NewOrder(order) => Some(&order.client_order_id),
+                | CancelOrder { client_order_id } => Some(client_order_id),
+                | ReplaceOrder {
+                    client_order_id, ..
+                } => Some(client_order_id),
+                | _ => None,
+            },
+            | _ => None,
+        }
+    }
+
+    /// The name of the method, as sent to the API.
+    pub fn method_name(&self) -> String {
+        match self {
+            | Self::Default { method, .. } | Self::ChannelRequest { method, .. } => {
+                method.to_string()
+            }
+            | Self::Raw { method, .. } => method.to_string(),
+        }
+    }
+
+    /// Creates a request for an arbitrary method, with the params
+    /// serialized as given.
+    pub fn raw(method: &'r str, params: serde_json::Value) -> Self {
+        Request::Raw {
+            method,
+            id: new_id(),
+            params,
         }
     }
 
-    pub fn login(private_key: &'r hmac::Key, public_key: &'r str) -> Self {
-        let timestamp = chrono::Utc::now().timestamp() * 1000;
-        let signature = hmac::sign(private_key, timestamp.to_string().as_bytes());
-        let signature = hex::encode(signature.as_ref());
+    /// Creates a login request, `signature` being the hex encoded
+    /// signature of the `timestamp` in milliseconds followed by the
+    /// `window`, if any.
+    pub fn login(api_key: &'r str, timestamp: i64, window: Option<u32>, signature: String) -> Self {
         Request::Default {
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
             method: Method::Login,
             params: Login {
                 r#type: "HS256",
-                api_key: public_key,
+                api_key,
                 timestamp,
+                window,
                 signature,
             },
         }
@@ -149,24 +229,49 @@ impl<'r> Request<'r> {
     /// # use cryptomkt::api::request::*;
     /// Request::subscribe("ticker".into(), vec!["ETHBTC".into()]);
     /// ```
-    pub fn subscribe(channel: &'r str, symbols: &'r [&'r str]) -> Self {
+    pub fn subscribe(channel: &'r str, symbols: &'r [impl AsRef<str>]) -> Self {
         Request::ChannelRequest {
             method: Method::Subscribe,
             ch: channel,
-            id: Utc::now().timestamp_nanos(),
-            params: Symbols { symbols },
+            id: new_id(),
+            params: Symbols {
+                symbols: symbols.iter().map(AsRef::as_ref).collect(),
+            },
         }
     }
 
-    pub fn subscribe_limit(channel: &'r str, symbols: &'r [&'r str], limit: i32) -> Self {
+    pub fn unsubscribe(channel: &'r str, symbols: &'r [impl AsRef<str>]) -> Self {
+        Request::ChannelRequest {
+            method: Method::Unsubscribe,
+            ch: channel,
+            id: new_id(),
+            params: Symbols {
+                symbols: symbols.iter().map(AsRef::as_ref).collect(),
+            },
+        }
+    }
+
+    pub fn subscribe_limit(channel: &'r str, symbols: &'r [impl AsRef<str>], limit: i32) -> Self {
+        let symbols = symbols.iter().map(AsRef::as_ref).collect();
         Request::ChannelRequest {
             method: Method::Subscribe,
             ch: channel,
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
             params: SymbolsLimit { symbols, limit },
         }
     }
 
+    /// Creates a subscription request for the channel provided with
+    /// arbitrary params.
+    pub fn subscribe_raw(channel: &'r str, params: serde_json::Value) -> Self {
+        Request::ChannelRequest {
+            method: Method::Subscribe,
+            ch: channel,
+            id: new_id(),
+            params: Raw(params),
+        }
+    }
+
     /// Returns the list of all active subscriptions on a channel.
     /// In case of a successful subscriptions, the server will send:
     /// for `ticker/price/{speed}`, `ticker/{speed}`,
@@ -175,27 +280,26 @@ impl<'r> Request<'r> {
     /// which embraces the changes that have occurred if any; for `trades`,
     /// `orderbook/full`, `candles/{period}`: snapshot (snapshot) and update
     /// (update) notifications.
-
     pub fn subscriptions(channel: &'r str) -> Self {
         Request::ChannelRequest {
             method: Method::Subscriptions,
             ch: channel,
             params: Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
     pub fn spot_subscribe() -> Self {
         Request::Default {
             method: Method::SpotSubscribe,
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
             params: Empty {},
         }
     }
     pub fn spot_cancel_order(client_order_id: &'r str) -> Self {
         Request::Default {
             method: Method::SpotCancelOrder,
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
             params: CancelOrder { client_order_id },
         }
     }
@@ -203,7 +307,7 @@ impl<'r> Request<'r> {
     pub fn spot_unsubscribe() -> Self {
         Request::Default {
             method: Method::SpotUnsubscribe,
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
             params: Empty {},
         }
     }
@@ -212,11 +316,11 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::SpotNewOrder,
             params: NewOrder(order),
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
-    pub fn spot_replace_order(client_order_id: &'r str, quantity: f64, price: f64) -> Self {
+    pub fn spot_replace_order(client_order_id: &'r str, quantity: Number, price: Number) -> Self {
         Request::Default {
             method: Method::SpotReplaceOrder,
             params: ReplaceOrder {
@@ -225,7 +329,7 @@ impl<'r> Request<'r> {
                 quantity,
                 price,
             },
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
@@ -233,7 +337,7 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::SpotCancelOrders,
             params: Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
@@ -241,7 +345,7 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::SpotBalances,
             params: Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
@@ -249,7 +353,7 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::SpotFees,
             params: Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
@@ -257,7 +361,7 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::SpotGetOrders,
             params: Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
@@ -265,7 +369,7 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::SpotFee,
             params: Symbol { symbol },
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
@@ -273,28 +377,28 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::SubscribeTransactions,
             params: Params::Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
     pub fn unsubscribe_transactions() -> Self {
         Request::Default {
             method: Method::UnsubscribeTransactions,
             params: Params::Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
     pub fn subscribe_wallet_balances() -> Self {
         Request::Default {
             method: Method::SubscribeWalletBalances,
             params: Params::Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
     pub fn unsubscribe_wallet_balances() -> Self {
         Request::Default {
             method: Method::UnsubscribeWalletBalances,
             params: Params::Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
@@ -302,7 +406,7 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::WalletBalances,
             params: Params::Empty {},
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 
@@ -310,16 +414,29 @@ impl<'r> Request<'r> {
         Request::Default {
             method: Method::WalletBalance,
             params: Params::WalletBalance { currency },
-            id: Utc::now().timestamp_nanos(),
+            id: new_id(),
         }
     }
 }
 
+pub(crate) fn new_id() -> i64 {
+    Utc::now().timestamp_nanos_opt().unwrap_or_default()
+}
+
 pub(crate) fn random_id() -> String {
     (0..)
-        .into_iter()
         .map(|_| rand::random())
         .filter(|c: &char| c.is_ascii_alphanumeric())
         .take(15)
         .collect()
 }
+
+#[test]
+fn serialize_raw_request() {
+    let params = serde_json::json!({"symbol": "ETHBTC"});
+    let request = Request::raw("spot_get_trades", params.clone());
+    let json = serde_json::to_value(&request).unwrap();
+    assert_eq!(json["method"], "spot_get_trades");
+    assert_eq!(json["params"], params);
+    assert_eq!(json["id"], request.id());
+}
@@ -0,0 +1,190 @@
+//! A decoder for the messages of the websocket API.
+//!
+//! Decoding `Response` through its untagged derive buffers the whole message
+//! and tries every variant in order. Instead, the decoder reads the keys that
+//! identify the message (`id`, `ch`, `method` and the key holding the
+//! payload) and deserializes the payload directly into its type.
+use super::*;
+use serde_json::value::RawValue;
+use serde_with::BorrowCow;
+use std::borrow::Cow;
+
+#[serde_as]
+#[derive(Deserialize)]
+struct Envelope<'a> {
+    id: Option<i64>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    #[serde(borrow, default)]
+    ch: Option<Cow<'a, str>>,
+    #[serde_as(as = "Option<BorrowCow>")]
+    #[serde(borrow, default)]
+    method: Option<Cow<'a, str>>,
+    #[serde(borrow)]
+    result: Option<&'a RawValue>,
+    #[serde(borrow)]
+    error: Option<&'a RawValue>,
+    #[serde(borrow)]
+    params: Option<&'a RawValue>,
+    #[serde(borrow)]
+    data: Option<&'a RawValue>,
+    #[serde(borrow)]
+    snapshot: Option<&'a RawValue>,
+    #[serde(borrow)]
+    update: Option<&'a RawValue>,
+}
+
+#[cfg(feature = "zero-copy")]
+impl<'a> MarketData<'a> {
+    /// Decodes a market data notification, borrowing the channel and
+    /// symbols from `msg`. Returns `None` for other messages.
+    pub fn decode(msg: &'a str) -> serde_json::Result<Option<MarketData<'a>>> {
+        let envelope: Envelope<'a> = serde_json::from_str(msg)?;
+        Ok(envelope.market_data())
+    }
+}
+
+impl Response {
+    /// Decodes a message sent by the websocket API. Messages that don't
+    /// match any known type are returned as `Response::Unknown`.
+    pub fn decode(msg: &str) -> serde_json::Result<Response> {
+        let envelope: Envelope = serde_json::from_str(msg)?;
+        match envelope.decode() {
+            | Some(response) => Ok(response),
+            | None => Ok(Response::Unknown(serde_json::from_str(msg)?)),
+        }
+    }
+}
+
+fn parse<'a, T: Deserialize<'a>>(raw: &'a RawValue) -> Option<T> {
+    serde_json::from_str(raw.get()).ok()
+}
+
+impl<'a> Envelope<'a> {
+    fn decode(&self) -> Option<Response> {
+        if let Some(error) = self.error {
+            let error = parse(error)?;
+            return Some(Response::Error(Error { error, id: self.id }));
+        }
+        if let Some(ch) = &self.ch {
+            let data = self.channel(ch)?;
+            let ch = ch.clone().into_owned();
+            return Some(Response::Channel(ChannelMessage { ch, data }));
+        }
+        if let (Some(method), Some(params)) = (&self.method, self.params) {
+            return self
+                .income_method(method, params)
+                .map(Response::IncomeMethods);
+        }
+        if let (Some(id), Some(result)) = (self.id, self.result) {
+            let result = parse(result)?;
+            return Some(Response::Result(APIResult { id, result }));
+        }
+        None
+    }
+
+    fn channel(&self, ch: &str) -> Option<ChannelVariant> {
+        use ChannelVariant::*;
+        let variant = match (self.data, self.snapshot, self.update) {
+            | (Some(data), ..) if ch.starts_with("ticker/price/") => {
+                PriceTicker { data: parse(data)? }
+            }
+            | (Some(data), ..) if ch.starts_with("ticker/") => Ticker { data: parse(data)? },
+            | (Some(data), ..) if ch.starts_with("orderbook/top/") => {
+                TopOrder { data: parse(data)? }
+            }
+            | (Some(data), ..) if ch.starts_with("orderbook/") => {
+                PartialOrderbook { data: parse(data)? }
+            }
+            | (_, Some(snapshot), _) if ch == "trades" => TradeSnapshot {
+                snapshot: parse(snapshot)?,
+            },
+            | (_, _, Some(update)) if ch == "trades" => TradeUpdate {
+                update: parse(update)?,
+            },
+            | (_, Some(snapshot), _) if ch == "orderbook/full" => OrderbookSnapshot {
+                snapshot: parse(snapshot)?,
+            },
+            | (_, _, Some(update)) if ch == "orderbook/full" => OrderbookUpdate {
+                update: parse(update)?,
+            },
+            | (_, Some(snapshot), _) if ch.starts_with("candles/") => CandleSnapshot {
+                snapshot: parse(snapshot)?,
+            },
+            | (_, _, Some(update)) if ch.starts_with("candles/") => CandleUpdate {
+                update: parse(update)?,
+            },
+            | _ => return None,
+        };
+        Some(variant)
+    }
+
+    #[cfg(feature = "zero-copy")]
+    fn market_data(&self) -> Option<MarketData<'a>> {
+        use MarketVariant::*;
+        // the channel is borrowed as long as it contains no escapes.
+        let ch = match self.ch {
+            | Some(Cow::Borrowed(ch)) => ch,
+            | _ => return None,
+        };
+        let data = match (self.data, self.snapshot, self.update) {
+            | (Some(data), ..) if ch.starts_with("orderbook/top/") => TopOrder(parse(data)?),
+            | (Some(data), ..) if ch.starts_with("orderbook/") => PartialOrderbook(parse(data)?),
+            | (_, Some(snapshot), _) if ch == "trades" => TradeSnapshot(parse(snapshot)?),
+            | (_, _, Some(update)) if ch == "trades" => TradeUpdate(parse(update)?),
+            | (_, Some(snapshot), _) if ch == "orderbook/full" => {
+                OrderbookSnapshot(parse(snapshot)?)
+            }
+            | (_, _, Some(update)) if ch == "orderbook/full" => OrderbookUpdate(parse(update)?),
+            | _ => return None,
+        };
+        Some(MarketData { ch, data })
+    }
+
+    fn income_method(&self, method: &str, params: &'a RawValue) -> Option<IncomeMethods> {
+        use IncomeMethods::*;
+        let method = match method {
+            | "spot_order" => SpotOrder {
+                params: parse(params)?,
+            },
+            | "spot_orders" => SpotOrders {
+                params: parse(params)?,
+            },
+            | "transaction_update" => TransactionUpdate {
+                params: parse(params)?,
+            },
+            | "wallet_balances" => WalletBalances {
+                params: parse(params)?,
+            },
+            | "wallet_balance_update" => WalletBalanceUpdate {
+                params: parse(params)?,
+            },
+            | _ => return None,
+        };
+        Some(method)
+    }
+}
+
+#[test]
+fn decode_messages() {
+    let orderbook = r#"{"ch":"orderbook/full","update":{"ETHBTC":{"t":1626866578796,"s":27617207,"a":[["0.060506","0"]],"b":[]}}}"#;
+    assert!(matches!(
+        Response::decode(orderbook).unwrap(),
+        Response::Channel(ChannelMessage {
+            data: ChannelVariant::OrderbookUpdate { .. },
+            ..
+        })
+    ));
+    let trades = r#"{"ch":"trades","snapshot":{"BTCCLP":[{"i":1635188320828,"p":"51711712","q":"0.01150","s":"sell","t":1635188320828}]}}"#;
+    assert!(Response::decode(trades).unwrap().as_trades().is_ok());
+    let orders = r#"{"jsonrpc":"2.0","method":"spot_orders","params":[]}"#;
+    assert!(Response::decode(orders).unwrap().as_spot_orders().is_ok());
+    let error = r#"{"id":3,"error":{"code":2001,"message":"Symbol not found"}}"#;
+    assert_eq!(Response::decode(error).unwrap().id(), Some(3));
+    let result = r#"{"id":4,"result":true}"#;
+    assert!(Response::decode(result).unwrap().success());
+    let unknown = r#"{"ch":"ticker/1s","data":{"ETHBTC":{"t":1614815872000}}}"#;
+    assert!(matches!(
+        Response::decode(unknown).unwrap(),
+        Response::Unknown(_)
+    ));
+}
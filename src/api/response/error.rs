@@ -12,10 +12,186 @@ pub struct Error {
     pub id: Option<i64>,
 }
 
+/// The error codes documented by the cryptomkt API.
+#[derive(Debug, Clone, Copy, Hash, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum ErrorCode {
+    /// Action is forbidden for account.
+    Forbidden,
+    /// Too many requests, the rate limit was exceeded.
+    RateLimit,
+    InternalServerError,
+    ServiceUnavailable,
+    GatewayTimeout,
+    /// Authorization is required.
+    AuthorizationRequired,
+    /// Authorization is required or has failed.
+    AuthorizationFailed,
+    /// Action is forbidden for this API key.
+    ApiKeyForbidden,
+    UnsupportedAuthorizationMethod,
+    SymbolNotFound,
+    CurrencyNotFound,
+    QuantityNotANumber,
+    QuantityTooLow,
+    BadQuantity,
+    PriceNotANumber,
+    PriceTooLow,
+    BadPrice,
+    ValidationError,
+    UserDisabled,
+    InsufficientFunds,
+    OrderNotFound,
+    LimitExceeded,
+    TransactionNotFound,
+    PayoutNotFound,
+    PayoutAlreadyCommitted,
+    PayoutAlreadyRolledBack,
+    DuplicateClientOrderId,
+    /// The price and quantity of a replaced order were not changed.
+    PriceAndQuantityNotChanged,
+    /// The exchange is temporarily closed.
+    ExchangeClosed,
+    InvalidPayoutAddress,
+    OffchainUnavailable,
+    /// The internal deadline to execute the order was exceeded.
+    ExecutionDeadlineExceeded,
+    /// A code not documented at the time of writing.
+    Other(i32),
+}
+
+use ErrorCode::*;
+
+impl From<i32> for ErrorCode {
+    fn from(code: i32) -> Self {
+        match code {
+            | 403 => Forbidden,
+            | 429 => RateLimit,
+            | 500 => InternalServerError,
+            | 503 => ServiceUnavailable,
+            | 504 => GatewayTimeout,
+            | 1001 => AuthorizationRequired,
+            | 1002 => AuthorizationFailed,
+            | 1003 => ApiKeyForbidden,
+            | 1004 => UnsupportedAuthorizationMethod,
+            | 2001 => SymbolNotFound,
+            | 2002 => CurrencyNotFound,
+            | 2010 => QuantityNotANumber,
+            | 2011 => QuantityTooLow,
+            | 2012 => BadQuantity,
+            | 2020 => PriceNotANumber,
+            | 2021 => PriceTooLow,
+            | 2022 => BadPrice,
+            | 10001 => ValidationError,
+            | 10021 => UserDisabled,
+            | 20001 => InsufficientFunds,
+            | 20002 => OrderNotFound,
+            | 20003 => LimitExceeded,
+            | 20004 => TransactionNotFound,
+            | 20005 => PayoutNotFound,
+            | 20006 => PayoutAlreadyCommitted,
+            | 20007 => PayoutAlreadyRolledBack,
+            | 20008 => DuplicateClientOrderId,
+            | 20009 => PriceAndQuantityNotChanged,
+            | 20010 => ExchangeClosed,
+            | 20011 => InvalidPayoutAddress,
+            | 20014 => OffchainUnavailable,
+            | 20080 => ExecutionDeadlineExceeded,
+            | code => Other(code),
+        }
+    }
+}
+
+impl ErrorCode {
+    pub fn code(self) -> i32 {
+        match self {
+            | Forbidden => 403,
+            | RateLimit => 429,
+            | InternalServerError => 500,
+            | ServiceUnavailable => 503,
+            | GatewayTimeout => 504,
+            | AuthorizationRequired => 1001,
+            | AuthorizationFailed => 1002,
+            | ApiKeyForbidden => 1003,
+            | UnsupportedAuthorizationMethod => 1004,
+            | SymbolNotFound => 2001,
+            | CurrencyNotFound => 2002,
+            | QuantityNotANumber => 2010,
+            | QuantityTooLow => 2011,
+            | BadQuantity => 2012,
+            | PriceNotANumber => 2020,
+            | PriceTooLow => 2021,
+            | BadPrice => 2022,
+            | ValidationError => 10001,
+            | UserDisabled => 10021,
+            | InsufficientFunds => 20001,
+            | OrderNotFound => 20002,
+            | LimitExceeded => 20003,
+            | TransactionNotFound => 20004,
+            | PayoutNotFound => 20005,
+            | PayoutAlreadyCommitted => 20006,
+            | PayoutAlreadyRolledBack => 20007,
+            | DuplicateClientOrderId => 20008,
+            | PriceAndQuantityNotChanged => 20009,
+            | ExchangeClosed => 20010,
+            | InvalidPayoutAddress => 20011,
+            | OffchainUnavailable => 20014,
+            | ExecutionDeadlineExceeded => 20080,
+            | Other(code) => code,
+        }
+    }
+
+    /// Whether the same request may succeed if it is sent again later.
+    pub fn is_retryable(self) -> bool {
+        matches!(
+            self,
+            RateLimit
+                | InternalServerError
+                | ServiceUnavailable
+                | GatewayTimeout
+                | ExchangeClosed
+                | ExecutionDeadlineExceeded
+        )
+    }
+
+    /// Whether the error is caused by missing, invalid or
+    /// insufficiently privileged credentials.
+    pub fn is_auth_failure(self) -> bool {
+        matches!(
+            self,
+            Forbidden
+                | AuthorizationRequired
+                | AuthorizationFailed
+                | ApiKeyForbidden
+                | UnsupportedAuthorizationMethod
+        )
+    }
+}
+
 impl Error {
     pub fn code(&self) -> i32 {
         self.error.code
     }
+
+    pub fn error_code(&self) -> ErrorCode {
+        ErrorCode::from(self.error.code)
+    }
+
+    pub fn message(&self) -> &str {
+        &self.error.message
+    }
+
+    pub fn description(&self) -> Option<&str> {
+        self.error.description.as_deref()
+    }
+
+    pub fn is_retryable(&self) -> bool {
+        self.error_code().is_retryable()
+    }
+
+    pub fn is_auth_failure(&self) -> bool {
+        self.error_code().is_auth_failure()
+    }
 }
 
 impl std::fmt::Display for Error {
@@ -28,3 +204,18 @@ impl std::fmt::Display for Error {
         Ok(())
     }
 }
+
+#[test]
+fn error_codes() {
+    let error: Error = serde_json::from_str(
+        r#"{"id": 123, "error": {"code": 20001, "message": "Insufficient funds", "description": "Check that the funds are sufficient"}}"#,
+    )
+    .unwrap();
+    assert_eq!(error.error_code(), InsufficientFunds);
+    assert!(!error.is_retryable());
+    for code in [403, 429, 1002, 2001, 20080, 12345] {
+        assert_eq!(ErrorCode::from(code).code(), code);
+    }
+    assert!(ErrorCode::from(429).is_retryable());
+    assert!(ErrorCode::from(1002).is_auth_failure());
+}
@@ -10,7 +10,6 @@ mod transaction;
 #[derive(Serialize, Deserialize, Clone, Debug)]
 #[serde(tag = "method")]
 #[serde(rename_all = "snake_case")]
-
 pub enum IncomeMethods {
     SpotOrder { params: Order },
     SpotOrders { params: Vec<Order> },
@@ -28,15 +28,15 @@ pub struct Order {
     pub time_in_force: TimeInForce,
     /// Order quantity.
     #[serde_as(as = "DisplayFromStr")]
-    pub quantity: f64,
+    pub quantity: Number,
 
     #[serde_as(as = "DisplayFromStr")]
     /// Cumulative executed quantity.
-    pub quantity_cumulative: f64,
+    pub quantity_cumulative: Number,
 
     /// Order price.
     #[serde_as(as = "DisplayFromStr")]
-    pub price: f64,
+    pub price: Number,
     /// A post-only order is an order that does not remove liquidity. If your
     /// post-only order causes a match with a pre-existing order as a taker,
     /// then the order will be cancelled.
@@ -46,6 +46,9 @@ pub struct Order {
     /// Date of the report's last update.
     pub updated_at: chrono::DateTime<chrono::Utc>,
     pub report_type: ReportType,
+    /// The account that placed the order.
+    #[serde(skip)]
+    pub account: Account,
 }
 
 use std::{fmt, fmt::Display};
@@ -70,6 +73,6 @@ impl Display for Order {
 #[test]
 fn order() {
     #[derive(Debug)]
-    struct FOO;
-    println!("|{:width$?}|", FOO, width = 10);
+    struct Foo;
+    println!("|{:width$?}|", Foo, width = 10);
 }
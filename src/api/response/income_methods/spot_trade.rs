@@ -10,20 +10,20 @@ pub struct SpotTrade {
     pub r#type: OrderType,
     pub time_in_force: TimeInForce,
     #[serde_as(as = "DisplayFromStr")]
-    pub quantity: f64,
+    pub quantity: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub quantity_cumulative: f64,
+    pub quantity_cumulative: Number,
     pub post_only: bool,
     pub created_at: chrono::DateTime<chrono::Utc>,
     pub updated_at: chrono::DateTime<chrono::Utc>,
     #[serde_as(as = "DisplayFromStr")]
     pub trade_id: i64,
     #[serde_as(as = "DisplayFromStr")]
-    pub trade_quantity: f64,
+    pub trade_quantity: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub trade_price: f64,
+    pub trade_price: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub trade_fee: f64,
+    pub trade_fee: Number,
     pub trade_taker: bool,
     pub report_type: ReportType,
 }
@@ -1,8 +1,13 @@
-pub use self::{error::Error, notification::*, result::*};
+pub use self::{
+    error::{Error, ErrorCode},
+    notification::*,
+    result::*,
+};
 use crate::prelude::*;
 pub use income_methods::*;
 pub use result::Balance;
 
+mod decode;
 mod error;
 mod income_methods;
 mod notification;
@@ -33,6 +38,10 @@ impl Response {
         match self {
             | Response::IncomeMethods(method) => Some(method.method().into()),
             | Response::Channel(channel) => Some(channel.ch.clone()),
+            | Response::Unknown(obj) => obj["ch"]
+                .as_str()
+                .or_else(|| obj["method"].as_str())
+                .map(Into::into),
             | _ => None,
         }
     }
@@ -71,6 +80,28 @@ impl Response {
             | res => Err(res),
         }
     }
+    // Decodes a notification of a channel sending snapshots and updates,
+    // `f` returning the variant back if it is of another channel.
+    fn into_event<T>(
+        self,
+        f: impl FnOnce(ChannelVariant) -> Result<Event<T>, ChannelVariant>,
+    ) -> Result<Event<T>, Self> {
+        match self {
+            | Response::Channel(ChannelMessage { ch, data }) => {
+                f(data).map_err(|data| Response::Channel(ChannelMessage { ch, data }))
+            }
+            | res => Err(res),
+        }
+    }
+    /// Like `as_orderbook`, but distinguishes the snapshots
+    /// of the full orderbook from its updates.
+    pub fn as_orderbook_event(self) -> Result<Event<OrderbookMap>, Self> {
+        self.into_event(|data| match data {
+            | ChannelVariant::OrderbookSnapshot { snapshot } => Ok(Event::Snapshot(snapshot)),
+            | ChannelVariant::OrderbookUpdate { update } => Ok(Event::Update(update)),
+            | data => Err(data),
+        })
+    }
     pub fn as_spot_fees(self) -> Result<Vec<Fee>, Self> {
         match self {
             | Response::Result(APIResult {
@@ -169,6 +200,29 @@ impl Response {
             | res => Err(res),
         }
     }
+    pub fn as_trades_event(self) -> Result<Event<TradeMap>, Self> {
+        self.into_event(|data| match data {
+            | ChannelVariant::TradeSnapshot { snapshot } => Ok(Event::Snapshot(snapshot)),
+            | ChannelVariant::TradeUpdate { update } => Ok(Event::Update(update)),
+            | data => Err(data),
+        })
+    }
+    pub fn as_price_ticker(self) -> Result<PriceTickerMap, Self> {
+        match self {
+            | Response::Channel(ChannelMessage {
+                data: ChannelVariant::PriceTicker { data },
+                ..
+            }) => Ok(data),
+            | res => Err(res),
+        }
+    }
+    pub fn as_candles(self) -> Result<Event<CandleMap>, Self> {
+        self.into_event(|data| match data {
+            | ChannelVariant::CandleSnapshot { snapshot } => Ok(Event::Snapshot(snapshot)),
+            | ChannelVariant::CandleUpdate { update } => Ok(Event::Update(update)),
+            | data => Err(data),
+        })
+    }
     pub fn as_spot_orders(self) -> Result<Vec<Order>, Self> {
         match self {
             | Response::Result(APIResult {
@@ -176,9 +230,19 @@ impl Response {
                 ..
             }) => Ok(spot_orders),
             | Response::IncomeMethods(IncomeMethods::SpotOrders { params }) => Ok(params),
-            | res => panic!("{:?}", res),
+            | res => Err(res),
         }
     }
+    // empty lists are decoded as the first list variant of the results.
+    pub(crate) fn is_empty_list(&self) -> bool {
+        matches!(
+            self,
+            Response::Result(APIResult {
+                result: ResultVariant::SpotOrders(orders),
+                ..
+            }) if orders.is_empty()
+        )
+    }
     pub fn success(self) -> bool {
         self.as_result()
             .map(|r| match r.result {
@@ -0,0 +1,61 @@
+//! Market data borrowed from the message it was decoded from.
+//!
+//! The owned notifications allocate a `String` for the symbol of every
+//! entry. `MarketData` instead keeps the symbols as slices of the message,
+//! and prices are parsed in place, so the only allocations left are the
+//! maps and the price levels themselves.
+use super::*;
+
+/// A map from symbols, borrowed from the message, to their values.
+pub type SymbolMap<'a, T> = HashMap<&'a str, T>;
+
+/// A market data notification decoded without copying its symbols.
+#[derive(Debug, Clone)]
+pub struct MarketData<'a> {
+    pub ch: &'a str,
+    pub data: MarketVariant<'a>,
+}
+
+#[derive(Debug, Clone)]
+pub enum MarketVariant<'a> {
+    OrderbookSnapshot(SymbolMap<'a, Orderbook>),
+    OrderbookUpdate(SymbolMap<'a, Orderbook>),
+    PartialOrderbook(SymbolMap<'a, Orderbook>),
+    TopOrder(SymbolMap<'a, TopOrder>),
+    TradeSnapshot(SymbolMap<'a, Vec<Trade>>),
+    TradeUpdate(SymbolMap<'a, Vec<Trade>>),
+}
+
+impl<'a> MarketData<'a> {
+    /// The latest exchange timestamp of the notification.
+    /// Trade snapshots are ignored, since they contain past trades.
+    pub(crate) fn timestamp(&self) -> Option<DateTime<Utc>> {
+        use MarketVariant::*;
+        match &self.data {
+            | OrderbookSnapshot(books) | OrderbookUpdate(books) | PartialOrderbook(books) => {
+                books.values().map(|book| book.timestamp).max()
+            }
+            | TopOrder(data) => data.values().map(|top| top.timestamp).max(),
+            | TradeUpdate(trades) => trades.values().flatten().map(|trade| trade.t).max(),
+            | TradeSnapshot(_) => None,
+        }
+    }
+}
+
+#[test]
+fn borrow_symbols() {
+    let msg = r#"{"ch":"orderbook/D5/100ms","data":{"ETHBTC":{"t":1626866578796,"s":27617207,"a":[["0.060506","1"]],"b":[["0.060439","4.4095"]]}}}"#;
+    let market = MarketData::decode(msg).unwrap().unwrap();
+    assert_eq!(market.ch, "orderbook/D5/100ms");
+    let timestamp = market.timestamp().unwrap();
+    assert_eq!(timestamp.timestamp_millis(), 1626866578796);
+    match market.data {
+        | MarketVariant::PartialOrderbook(books) => {
+            let (symbol, _) = books.iter().next().unwrap();
+            assert!(msg.as_bytes().as_ptr_range().contains(&symbol.as_ptr()));
+        }
+        | data => panic!("unexpected data: {:?}", data),
+    }
+    let result = r#"{"id":4,"result":true}"#;
+    assert!(MarketData::decode(result).unwrap().is_none());
+}
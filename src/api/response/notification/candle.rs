@@ -1,59 +1,49 @@
 use super::*;
-use async_tungstenite::tungstenite::handshake::client::Response;
-use serde_with::{serde_as, DisplayFromStr};
+use chrono::serde::ts_milliseconds;
 
-#[serde_as]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct Ticker(HashMap<String, TickerData>);
+/// The candles of each symbol of a `candles/{period}` subscription.
+pub type CandleMap = HashMap<String, Vec<Candle>>;
 
 #[serde_as]
-#[derive(Serialize, Deserialize, Debug, Clone)]
-
-pub struct TickerData {
-    #[serde(rename = "a")]
-    #[serde_as(as = "DisplayFromStr")]
-    pub ask_price: f32,
-
-    #[serde(rename = "A")]
-    #[serde_as(as = "DisplayFromStr")]
-    pub ask_quantity: f32,
-
-    #[serde(rename = "b")]
-    #[serde_as(as = "DisplayFromStr")]
-    pub bid_price: f32,
-
-    #[serde(rename = "B")]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Candle {
+    /// The time the candle opened at.
+    #[serde(rename = "t", with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "o")]
     #[serde_as(as = "DisplayFromStr")]
-    pub bid_quantity: f32,
-
+    pub open: Number,
     #[serde(rename = "c")]
     #[serde_as(as = "DisplayFromStr")]
-    pub close: f32,
-
-    #[serde(rename = "o")]
+    pub close: Number,
+    #[serde(rename = "h")]
     #[serde_as(as = "DisplayFromStr")]
-    pub open: f32,
-
+    pub high: Number,
     #[serde(rename = "l")]
     #[serde_as(as = "DisplayFromStr")]
-    pub low: f32,
-
-    #[serde(rename = "h")]
-    #[serde_as(as = "DisplayFromStr")]
-    pub high: f32,
-
+    pub low: Number,
+    /// The volume in the base currency.
     #[serde(rename = "v")]
     #[serde_as(as = "DisplayFromStr")]
-    pub volume: f32,
-
+    pub volume: Number,
+    /// The volume in the quote currency.
     #[serde(rename = "q")]
     #[serde_as(as = "DisplayFromStr")]
-    pub volume_quote: f32,
-
-    #[serde(rename = "L")]
-    pub id: i64,
-
-    #[serde(rename = "t")]
-    #[serde(with = "ts_seconds")]
-    pub timestamp: chrono::DateTime<chrono::Utc>,
-}
\ No newline at end of file
+    pub volume_quote: Number,
+}
+
+#[test]
+fn deserialize_candles() {
+    let msg = r#"{"ch":"candles/M1","snapshot":{"BTCUSDT":[{"t":1626860340000,"o":"30881.95","c":"30890.96","h":"30900.8","l":"30861.27","v":"1.27852","q":"39493.9021811"}]}}"#;
+    let response = Response::decode(msg).unwrap();
+    let candles = match response.as_candles().unwrap() {
+        | Event::Snapshot(candles) => candles,
+        | event => panic!("unexpected event: {:?}", event),
+    };
+    let candle = &candles["BTCUSDT"][0];
+    assert_eq!(candle.timestamp.timestamp_millis(), 1626860340000);
+    assert_eq!(candle.high, "30900.8".parse::<Number>().unwrap());
+    let msg = msg.replace("snapshot", "update");
+    let from_untagged: Response = serde_json::from_str(&msg).unwrap();
+    assert!(matches!(from_untagged.as_candles(), Ok(Event::Update(_))));
+}
@@ -1,9 +1,17 @@
 use crate::{api::Side, prelude::*};
+#[cfg(feature = "zero-copy")]
+pub use borrowed::*;
+pub use candle::*;
 pub use orderbook::*;
+pub use price_ticker::*;
 pub use ticker::*;
 pub use top_order::*;
 pub use trades::*;
+#[cfg(feature = "zero-copy")]
+mod borrowed;
+mod candle;
 mod orderbook;
+mod price_ticker;
 mod ticker;
 mod top_order;
 mod trades;
@@ -44,4 +52,67 @@ pub enum ChannelVariant {
     TradeUpdate {
         update: TradeMap,
     },
+
+    PriceTicker {
+        data: PriceTickerMap,
+    },
+
+    CandleSnapshot {
+        snapshot: CandleMap,
+    },
+    CandleUpdate {
+        update: CandleMap,
+    },
+}
+
+/// A notification of a channel that sends a snapshot of its
+/// data when subscribed to, followed by updates.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event<T> {
+    Snapshot(T),
+    Update(T),
+}
+
+impl<T> Event<T> {
+    pub fn is_snapshot(&self) -> bool {
+        matches!(self, Event::Snapshot(_))
+    }
+
+    pub fn data(&self) -> &T {
+        match self {
+            | Event::Snapshot(data) | Event::Update(data) => data,
+        }
+    }
+
+    pub fn into_data(self) -> T {
+        match self {
+            | Event::Snapshot(data) | Event::Update(data) => data,
+        }
+    }
+
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Event<U> {
+        match self {
+            | Event::Snapshot(data) => Event::Snapshot(f(data)),
+            | Event::Update(data) => Event::Update(f(data)),
+        }
+    }
+}
+
+impl ChannelMessage {
+    /// The latest exchange timestamp of the notification.
+    /// Trade snapshots are ignored, since they contain past trades.
+    pub(crate) fn timestamp(&self) -> Option<DateTime<Utc>> {
+        use ChannelVariant::*;
+        match &self.data {
+            | Ticker { data } => data.0.values().map(|ticker| ticker.timestamp).max(),
+            | OrderbookUpdate { update: books }
+            | OrderbookSnapshot { snapshot: books }
+            | PartialOrderbook { data: books } => books.values().map(|book| book.timestamp).max(),
+            | TopOrder { data } => data.values().map(|top| top.timestamp).max(),
+            | PriceTicker { data } => data.values().map(|ticker| ticker.timestamp).max(),
+            | TradeUpdate { update } => update.timestamp(),
+            // candles are timestamped with the time they opened at.
+            | TradeSnapshot { .. } | CandleSnapshot { .. } | CandleUpdate { .. } => None,
+        }
+    }
 }
@@ -1,18 +1,72 @@
 use crate::prelude::*;
 
+// the representation of a price level in the messages of the API.
 #[serde_as]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct BookOrder(
-    #[serde_as(as = "DisplayFromStr")] f64,
-    #[serde_as(as = "DisplayFromStr")] f64,
+    #[serde_as(as = "DisplayFromStr")] Number,
+    #[serde_as(as = "DisplayFromStr")] Number,
 );
 
+/// A price level of an orderbook.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(from = "BookOrder", into = "BookOrder")]
+pub struct PriceLevel {
+    pub price: Number,
+    /// The total quantity at the price. In updates, a quantity
+    /// of zero means the level was removed.
+    pub quantity: Number,
+}
+
+/// The orderbooks of each symbol of a notification.
+pub type OrderbookMap = HashMap<String, Orderbook>;
+
+/// The orderbook of a symbol, either a snapshot or an update.
+/// In updates, only the levels that changed are included.
+#[serde_as]
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct Orderbook {
-    t: i64,
-    s: i64,
-    a: Vec<BookOrder>,
-    b: Vec<BookOrder>,
+    #[serde(rename = "t", with = "chrono::serde::ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    /// The sequence number of the notification. Updates of a symbol
+    /// increase it by one, so a jump means an update was missed.
+    #[serde(rename = "s")]
+    pub sequence: i64,
+    #[serde(rename = "a")]
+    asks: Vec<PriceLevel>,
+    #[serde(rename = "b")]
+    bids: Vec<PriceLevel>,
+}
+
+impl PriceLevel {
+    /// Whether the update removes the level from the book.
+    pub fn is_deletion(&self) -> bool {
+        self.quantity == Number::default()
+    }
+}
+
+impl Orderbook {
+    /// The asks, from the lowest price to the highest.
+    pub fn asks(&self) -> &[PriceLevel] {
+        &self.asks
+    }
+
+    /// The bids, from the highest price to the lowest.
+    pub fn bids(&self) -> &[PriceLevel] {
+        &self.bids
+    }
+}
+
+impl From<BookOrder> for PriceLevel {
+    fn from(BookOrder(price, quantity): BookOrder) -> Self {
+        PriceLevel { price, quantity }
+    }
+}
+
+impl From<PriceLevel> for BookOrder {
+    fn from(level: PriceLevel) -> Self {
+        BookOrder(level.price, level.quantity)
+    }
 }
 
 #[test]
@@ -39,5 +93,16 @@ fn deserialize_orderbook() {
     }
 }
 "#;
-    serde_json::from_str::<super::ChannelMessage>(ticker).unwrap();
+    let message = serde_json::from_str::<super::ChannelMessage>(ticker).unwrap();
+    let book = &Response::Channel(message).as_orderbook().unwrap()["ETHBTC"];
+    assert_eq!(book.timestamp.timestamp_millis(), 1626866578796);
+    assert_eq!(book.sequence, 27617207);
+    assert_eq!(
+        book.asks()[1].quantity,
+        "12.6431".parse::<Number>().unwrap()
+    );
+    assert!(book.asks()[0].is_deletion());
+    assert!(!book.bids()[0].is_deletion());
+    let json = serde_json::to_value(book).unwrap();
+    assert_eq!(json["b"][2], serde_json::json!(["0.060407", "7.3349"]));
 }
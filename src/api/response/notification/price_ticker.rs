@@ -0,0 +1,62 @@
+use super::*;
+use chrono::serde::ts_milliseconds;
+
+/// The mini-tickers of each symbol of a `ticker/price/{speed}` subscription.
+pub type PriceTickerMap = HashMap<String, PriceTicker>;
+
+/// A compact ticker, without the best bid and ask of `TickerData`.
+#[serde_as]
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PriceTicker {
+    #[serde(rename = "t", with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+    /// The price of the last trade.
+    #[serde(rename = "c")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub last: Number,
+    #[serde(rename = "o")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub open: Number,
+    #[serde(rename = "h")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub high: Number,
+    #[serde(rename = "l")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub low: Number,
+    /// The volume in the base currency.
+    #[serde(rename = "v")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub volume: Number,
+    /// The volume in the quote currency.
+    #[serde(rename = "q")]
+    #[serde_as(as = "DisplayFromStr")]
+    pub volume_quote: Number,
+    /// The change of the last price from the open price.
+    #[serde(rename = "p", default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price_change: Option<Number>,
+    /// The change of the last price from the open price, in percent.
+    #[serde(rename = "P", default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price_change_percent: Option<Number>,
+}
+
+#[test]
+fn deserialize_price_ticker() {
+    let msg = r#"{"ch":"ticker/price/1s","data":{"BTCUSDT":{"t":1614815872030,"o":"32636.79","c":"32085.51","h":"33379.92","l":"30683.28","v":"11.90667","q":"384081.1955629","p":"-551.28","P":"-1.69"}}}"#;
+    let tickers = Response::decode(msg).unwrap().as_price_ticker().unwrap();
+    let ticker = &tickers["BTCUSDT"];
+    assert_eq!(ticker.timestamp.timestamp_millis(), 1614815872030);
+    assert_eq!(ticker.last, "32085.51".parse::<Number>().unwrap());
+    assert_eq!(ticker.price_change, Some("-551.28".parse().unwrap()));
+    let from_untagged: Response = serde_json::from_str(msg).unwrap();
+    assert!(from_untagged.as_price_ticker().is_ok());
+}
+
+#[test]
+fn deserialize_price_ticker_batch() {
+    let batch = r#"{"ch":"ticker/price/1s/batch","data":{"BTCUSDT":{"t":1614815872030,"o":"32636.79","c":"32085.51","h":"33379.92","l":"30683.28","v":"11.90667","q":"384081.1955629"},"ETHBTC":{"t":1614815872050,"o":"0.030781","c":"0.031210","h":"0.031788","l":"0.030733","v":"62.587","q":"1.951420577"}}}"#;
+    let tickers = Response::decode(batch).unwrap().as_price_ticker().unwrap();
+    assert_eq!(tickers.len(), 2);
+    assert_eq!(tickers["ETHBTC"].price_change, None);
+}
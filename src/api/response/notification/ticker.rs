@@ -1,6 +1,7 @@
 use serde_with::{serde_as, DisplayFromStr};
 
 use super::*;
+use chrono::serde::ts_milliseconds;
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -12,49 +13,72 @@ pub struct Ticker(pub HashMap<String, TickerData>);
 pub struct TickerData {
     #[serde(rename = "a")]
     #[serde_as(as = "DisplayFromStr")]
-    pub ask_price: f64,
+    pub ask_price: Number,
 
     #[serde(rename = "A")]
     #[serde_as(as = "DisplayFromStr")]
-    pub ask_quantity: f64,
+    pub ask_quantity: Number,
 
     #[serde(rename = "b")]
     #[serde_as(as = "DisplayFromStr")]
-    pub bid_price: f64,
+    pub bid_price: Number,
 
     #[serde(rename = "B")]
     #[serde_as(as = "DisplayFromStr")]
-    pub bid_quantity: f64,
+    pub bid_quantity: Number,
 
     #[serde(rename = "c")]
     #[serde_as(as = "DisplayFromStr")]
-    pub close: f64,
+    pub close: Number,
 
     #[serde(rename = "o")]
     #[serde_as(as = "DisplayFromStr")]
-    pub open: f64,
+    pub open: Number,
 
     #[serde(rename = "l")]
     #[serde_as(as = "DisplayFromStr")]
-    pub low: f64,
+    pub low: Number,
 
     #[serde(rename = "h")]
     #[serde_as(as = "DisplayFromStr")]
-    pub high: f64,
+    pub high: Number,
 
     #[serde(rename = "v")]
     #[serde_as(as = "DisplayFromStr")]
-    pub volume: f64,
+    pub volume: Number,
 
     #[serde(rename = "q")]
     #[serde_as(as = "DisplayFromStr")]
-    pub volume_quote: f64,
+    pub volume_quote: Number,
 
+    /// The change of the last price from the open price.
+    #[serde(rename = "p", default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price_change: Option<Number>,
+
+    /// The change of the last price from the open price, in percent.
+    #[serde(rename = "P", default)]
+    #[serde_as(as = "Option<DisplayFromStr>")]
+    pub price_change_percent: Option<Number>,
+
+    /// The id of the last trade.
     #[serde(rename = "L")]
     pub id: i64,
 
-    #[serde(rename = "t")]
-    pub timestamp: u64,
+    #[serde(rename = "t", with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
+}
+
+impl TickerData {
+    /// The difference between the best ask and the best bid.
+    pub fn spread(&self) -> Number {
+        self.ask_price - self.bid_price
+    }
+
+    /// The price halfway between the best ask and the best bid.
+    pub fn mid(&self) -> Number {
+        (self.ask_price + self.bid_price) / Number::from(2)
+    }
 }
 
 #[test]
@@ -80,5 +104,21 @@ fn deserialize_ticker() {
         }
         }
     }"#;
-    serde_json::from_str::<super::ChannelMessage>(ticker).unwrap();
+    let message = serde_json::from_str::<super::ChannelMessage>(ticker).unwrap();
+    let ticker = &Response::Channel(message).as_ticker().unwrap().0["ETHBTC"];
+    assert_eq!(ticker.timestamp.timestamp_millis(), 1614815872000);
+    assert_eq!(ticker.price_change_percent, Some("1.39".parse().unwrap()));
+    assert_eq!(ticker.spread(), ticker.ask_price - ticker.bid_price);
+    assert!(ticker.bid_price < ticker.mid() && ticker.mid() < ticker.ask_price);
+}
+
+#[test]
+fn deserialize_ticker_batch() {
+    let batch = r#"{"ch":"ticker/1s/batch","data":{"BTCUSDT":{"t":1614815872000,"a":"32092.95","A":"0.00018","b":"32085.51","B":"0.12000","c":"32085.51","o":"32636.79","h":"33379.92","l":"30683.28","v":"11.90667","q":"384081.1955629","L":1182694927},"ETHBTC":{"t":1614815872050,"a":"0.031175","A":"0.03329","b":"0.031148","B":"0.10565","c":"0.031210","o":"0.030781","h":"0.031788","l":"0.030733","v":"62.587","q":"1.951420577","L":1182694928}}}"#;
+    let response = Response::decode(batch).unwrap();
+    assert_eq!(response.method().as_deref(), Some("ticker/1s/batch"));
+    let tickers = response.as_ticker().unwrap().0;
+    assert_eq!(tickers.len(), 2);
+    assert_eq!(tickers["ETHBTC"].id, 1182694928);
+    assert_eq!(tickers["BTCUSDT"].price_change, None);
 }
@@ -1,21 +1,34 @@
 use crate::api::*;
+use chrono::serde::ts_milliseconds;
 
 pub type TopOrderMap = HashMap<String, TopOrder>;
 #[serde_as]
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TopOrder {
-    #[serde(rename = "t")]
-    pub timestamp: u64,
+    #[serde(rename = "t", with = "ts_milliseconds")]
+    pub timestamp: DateTime<Utc>,
     #[serde(rename = "a")]
     #[serde_as(as = "DisplayFromStr")]
-    pub ask_price: f64,
+    pub ask_price: Number,
     #[serde(rename = "A")]
     #[serde_as(as = "DisplayFromStr")]
-    pub ask_quantity: f64,
+    pub ask_quantity: Number,
     #[serde(rename = "b")]
     #[serde_as(as = "DisplayFromStr")]
-    pub bid_price: f64,
+    pub bid_price: Number,
     #[serde(rename = "B")]
     #[serde_as(as = "DisplayFromStr")]
-    pub bid_quantity: f64,
+    pub bid_quantity: Number,
+}
+
+impl TopOrder {
+    /// The difference between the best ask and the best bid.
+    pub fn spread(&self) -> Number {
+        self.ask_price - self.bid_price
+    }
+
+    /// The price halfway between the best ask and the best bid.
+    pub fn mid(&self) -> Number {
+        (self.ask_price + self.bid_price) / Number::from(2)
+    }
 }
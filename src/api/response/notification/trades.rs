@@ -1,24 +1,77 @@
 use super::*;
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
-pub struct TradeMap(HashMap<String, Vec<Trade>>);
+pub struct TradeMap(pub HashMap<String, Vec<Trade>>);
+
+impl TradeMap {
+    pub(super) fn timestamp(&self) -> Option<DateTime<Utc>> {
+        self.0.values().flatten().map(|trade| trade.t).max()
+    }
+
+    /// The trades of every symbol. The symbols are in no particular order,
+    /// and the trades of each symbol are in the order they were received.
+    pub fn trades(&self) -> impl Iterator<Item = (&str, PublicTrade)> + '_ {
+        self.0.iter().flat_map(|(symbol, trades)| {
+            trades
+                .iter()
+                .map(move |trade| (symbol.as_str(), PublicTrade::from(trade)))
+        })
+    }
+}
 
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Trade {
-    pub t: i64,
+    #[serde(with = "chrono::serde::ts_milliseconds")]
+    pub t: DateTime<Utc>,
     pub i: i64,
     #[serde_as(as = "DisplayFromStr")]
-    pub p: f64,
+    pub p: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub q: f64,
+    pub q: Number,
     pub s: Side,
 }
 
+/// A trade of the public trades channel.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PublicTrade {
+    pub id: i64,
+    pub price: Number,
+    pub quantity: Number,
+    /// The side of the taker of the trade.
+    pub side: Side,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<&Trade> for PublicTrade {
+    fn from(trade: &Trade) -> Self {
+        PublicTrade {
+            id: trade.i,
+            price: trade.p,
+            quantity: trade.q,
+            side: trade.s,
+            timestamp: trade.t,
+        }
+    }
+}
+
+impl From<Trade> for PublicTrade {
+    fn from(trade: Trade) -> Self {
+        PublicTrade::from(&trade)
+    }
+}
+
 #[test]
 fn serialize_trade() {
-    let _: super::ChannelMessage = serde_json::from_str(
+    let message: super::ChannelMessage = serde_json::from_str(
         r#"{"ch":"trades","update":{"BTCCLP":[{"i":1635188320828,"p":"51711712","q":"0.01150","s":"sell","t":1635188320828}]}}"#,
     )
     .unwrap();
+    let trades = Response::Channel(message).as_trades().unwrap();
+    let (symbol, trade) = trades.trades().next().unwrap();
+    assert_eq!(symbol, "BTCCLP");
+    assert_eq!(trade.id, 1635188320828);
+    assert_eq!(trade.price, "51711712".parse::<Number>().unwrap());
+    assert_eq!(trade.side, Side::Sell);
+    assert_eq!(trade.timestamp.timestamp_millis(), 1635188320828);
 }
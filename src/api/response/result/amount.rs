@@ -5,7 +5,7 @@ use serde_with::{serde_as, DisplayFromStr};
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
 pub struct Amount {
     #[serde_as(as = "DisplayFromStr")]
-    pub available: f64,
+    pub available: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub reserved: f64,
+    pub reserved: Number,
 }
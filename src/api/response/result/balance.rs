@@ -5,7 +5,10 @@ use crate::prelude::*;
 pub struct Balance {
     pub currency: String,
     #[serde_as(as = "DisplayFromStr")]
-    pub available: f64,
+    pub available: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub reserved: f64,
+    pub reserved: Number,
+    /// The account holding the balance.
+    #[serde(skip)]
+    pub account: crate::api::Account,
 }
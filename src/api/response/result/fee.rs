@@ -3,9 +3,9 @@ use crate::prelude::*;
 #[serde_as]
 #[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct Fee {
-    symbol: String,
+    pub symbol: String,
     #[serde_as(as = "DisplayFromStr")]
-    take_rate: f64,
+    pub take_rate: Number,
     #[serde_as(as = "DisplayFromStr")]
-    make_rate: f64,
+    pub make_rate: Number,
 }
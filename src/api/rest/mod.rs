@@ -9,13 +9,13 @@ pub struct SymbolInfo {
     pub base_currency: String,
     pub quote_currency: String,
     #[serde_as(as = "DisplayFromStr")]
-    pub quantity_increment: f64,
+    pub quantity_increment: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub tick_size: f64,
+    pub tick_size: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub take_rate: f64,
+    pub take_rate: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub make_rate: f64,
+    pub make_rate: Number,
     pub fee_currency: String,
 }
 
@@ -25,13 +25,28 @@ pub struct SymbolInfo {
 pub struct RestTrade {
     pub id: u64,
     #[serde_as(as = "DisplayFromStr")]
-    pub price: f64,
+    pub price: Number,
     #[serde_as(as = "DisplayFromStr")]
-    pub qty: f64,
+    pub qty: Number,
     pub side: Side,
     pub timestamp: chrono::DateTime<Utc>,
 }
 
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SubAccount {
+    pub sub_account_id: String,
+    pub email: String,
+    /// Possible values: `new`, `active`, `disable`
+    pub status: String,
+}
+
+/// The balances of a sub-account.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct SubAccountBalance {
+    pub wallet: Vec<Balance>,
+    pub spot: Vec<Balance>,
+}
+
 use std::fmt::{self, *};
 impl Display for SymbolInfo {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
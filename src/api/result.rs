@@ -67,54 +67,3 @@ pub struct CandleData {
     #[serde_as(as = "DisplayFromStr")]
     quote_volume: f64,
 }
-
-
-// #[derive(Serialize, Deserialize, Debug, Clone)]
-// pub enum Channel {
-//     // Trades
-//     #[serde(rename = "trades")]
-//     Trades,
-
-//     // MiniTikers
-//     #[serde(rename = "ticker/price/1s")]
-//     TickerPrice1S,
-//     #[serde(rename = "ticker/price/3s")]
-//     TickerPrice3S,
-
-//     // MiniTickers Batches
-//     #[serde(rename = "ticker/price/1s/batch")]
-//     TickerPrice1SBatch,
-//     #[serde(rename = "ticker/price/3s/batch")]
-//     TickerPrice3SBatch,
-//     // Tickers
-//     #[serde(rename = "ticker/1s")]
-//     Ticker1s,
-//     #[serde(rename = "ticker/3s")]
-//     Ticker3s,
-//     #[serde(rename = "ticker/1s/batch")]
-//     Ticker1sBatch,
-//     #[serde(rename = "ticker/3s/batch")]
-//     Ticker3sBatch,
-//     // Orderbooks
-//     #[serde(rename = "orderbook/full")]
-//     OrderbookFull,
-//     // Candels
-//     #[serde(rename = "candles/M1")]
-//     CandlesM1,
-//     #[serde(rename = "candles/M3")]
-//     CandlesM3,
-//     #[serde(rename = "candles/M15")]
-//     CandlesM15,
-//     #[serde(rename = "candles/M30")]
-//     CandlesM30,
-//     #[serde(rename = "candles/H1")]
-//     CandlesH1,
-//     #[serde(rename = "candles/H4")]
-//     CandlesH4,
-//     #[serde(rename = "candles/D1")]
-//     CandlesD1,
-//     #[serde(rename = "candles/D7")]
-//     CandlesD7,
-//     #[serde(rename = "candles/1M")]
-//     Candles1M,
-// }
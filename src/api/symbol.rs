@@ -0,0 +1,206 @@
+//! # Symbols
+//! Symbols and currencies validated against the metadata of the exchange.
+//! ```no_run
+//! # #[cfg(feature = "rest-client")]
+//! # async fn doc() -> Result<(), cryptomarket::Error> {
+//! use cryptomarket::RestClient;
+//!
+//! let symbols = RestClient::new().symbols().await?;
+//! let symbol = symbols.symbol("BTCCLP")?;
+//! assert_eq!(symbol.base().as_str(), "BTC");
+//! assert!(symbols.symbol("BTCLCP").is_err());
+//! # Ok(()) }
+//! ```
+use crate::api::{SymbolInfo, SymbolsInfo};
+use crate::prelude::*;
+use std::fmt::{self, Display, Formatter};
+
+/// A currency supported by the exchange.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Ord, PartialOrd, Serialize)]
+#[serde(transparent)]
+pub struct Currency(String);
+
+/// A symbol supported by the exchange, such as `BTCCLP`.
+#[derive(Debug, Clone, Hash, Eq, PartialEq, Serialize)]
+#[serde(into = "String")]
+pub struct Symbol {
+    name: String,
+    base: Currency,
+    quote: Currency,
+}
+
+/// The symbols and currencies of the exchange, used
+/// to validate identifiers before they are sent.
+#[derive(Debug, Clone, Default)]
+pub struct Symbols {
+    symbols: HashMap<String, Symbol>,
+    currencies: HashMap<String, Currency>,
+    info: SymbolsInfo,
+}
+
+impl Currency {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Symbol {
+    pub fn as_str(&self) -> &str {
+        &self.name
+    }
+
+    /// The currency being bought or sold.
+    pub fn base(&self) -> &Currency {
+        &self.base
+    }
+
+    /// The currency prices are quoted in.
+    pub fn quote(&self) -> &Currency {
+        &self.quote
+    }
+}
+
+impl Symbols {
+    /// Looks up a symbol, failing with `Error::ParsingSymbol`
+    /// if the exchange does not list it.
+    pub fn symbol(&self, name: &str) -> Result<Symbol> {
+        self.symbols
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::ParsingSymbol(name.into()))
+    }
+
+    /// Validates a list of symbols, such as the ones passed to a subscription.
+    pub fn symbols(&self, names: &[impl AsRef<str>]) -> Result<Vec<Symbol>> {
+        names
+            .iter()
+            .map(|name| self.symbol(name.as_ref()))
+            .collect()
+    }
+
+    /// Looks up a currency, failing with `Error::ParsingCurrency`
+    /// if no symbol of the exchange trades it.
+    pub fn currency(&self, name: &str) -> Result<Currency> {
+        self.currencies
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::ParsingCurrency(name.into()))
+    }
+
+    /// The metadata of a symbol, such as its tick size and fees.
+    pub fn info(&self, symbol: &Symbol) -> Option<&SymbolInfo> {
+        self.info.get(symbol.as_str())
+    }
+
+    /// The symbols trading the currency, either as base or as quote.
+    pub fn with_currency<'a>(&'a self, currency: &'a Currency) -> impl Iterator<Item = &'a Symbol> {
+        self.iter()
+            .filter(move |symbol| symbol.base == *currency || symbol.quote == *currency)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Symbol> {
+        self.symbols.values()
+    }
+}
+
+impl From<SymbolsInfo> for Symbols {
+    fn from(info: SymbolsInfo) -> Self {
+        let mut symbols = HashMap::new();
+        let mut currencies = HashMap::new();
+        for (name, symbol) in &info {
+            let base = Currency(symbol.base_currency.clone());
+            let quote = Currency(symbol.quote_currency.clone());
+            currencies.insert(base.0.clone(), base.clone());
+            currencies.insert(quote.0.clone(), quote.clone());
+            let symbol = Symbol {
+                name: name.clone(),
+                base,
+                quote,
+            };
+            symbols.insert(name.clone(), symbol);
+        }
+        Symbols {
+            symbols,
+            currencies,
+            info,
+        }
+    }
+}
+
+impl Display for Currency {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl Display for Symbol {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name)
+    }
+}
+
+impl AsRef<str> for Currency {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for Symbol {
+    fn as_ref(&self) -> &str {
+        &self.name
+    }
+}
+
+impl From<Symbol> for String {
+    fn from(symbol: Symbol) -> Self {
+        symbol.name
+    }
+}
+
+impl From<&Symbol> for String {
+    fn from(symbol: &Symbol) -> Self {
+        symbol.name.clone()
+    }
+}
+
+#[test]
+fn validate_symbols() {
+    let info: SymbolsInfo = serde_json::from_str(
+        r#"{
+        "BTCCLP": {"type": "spot", "base_currency": "BTC", "quote_currency": "CLP", "quantity_increment": "0.00001", "tick_size": "1", "take_rate": "0.0025", "make_rate": "0.0015", "fee_currency": "CLP"},
+        "ETHBTC": {"type": "spot", "base_currency": "ETH", "quote_currency": "BTC", "quantity_increment": "0.0001", "tick_size": "0.000001", "take_rate": "0.0025", "make_rate": "0.0015", "fee_currency": "BTC"}
+    }"#,
+    )
+    .unwrap();
+    let symbols = Symbols::from(info);
+    let symbol = symbols.symbol("BTCCLP").unwrap();
+    assert_eq!(symbol.base().as_str(), "BTC");
+    assert_eq!(symbol.quote().as_str(), "CLP");
+    assert_eq!(serde_json::to_value(&symbol).unwrap(), "BTCCLP");
+    assert!(matches!(
+        symbols.symbol("BTCLCP"),
+        Err(Error::ParsingSymbol(_))
+    ));
+    assert!(matches!(
+        symbols.currency("BTX"),
+        Err(Error::ParsingCurrency(_))
+    ));
+    let btc = symbols.currency("BTC").unwrap();
+    assert_eq!(symbols.with_currency(&btc).count(), 2);
+    assert!(symbols.symbols(&["BTCCLP", "ETHBTC"]).is_ok());
+
+    // validated symbols are accepted where symbols are expected.
+    let request = crate::api::Request::subscribe("trades", std::slice::from_ref(&symbol));
+    assert_eq!(
+        serde_json::to_value(&request).unwrap()["params"]["symbols"][0],
+        "BTCCLP"
+    );
+    let order = crate::api::NewOrderBuilder::default()
+        .symbol(&symbol)
+        .side(crate::Side::Buy)
+        .price(Number::from(1))
+        .quantity(Number::from(1))
+        .build()
+        .unwrap();
+    assert_eq!(order.symbol, "BTCCLP");
+}
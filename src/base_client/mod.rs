@@ -1,13 +1,13 @@
 use crate::type_alias::*;
 use futures::{StreamExt, *};
-use requests::Requests;
+use requests::{Pending, Reply, Requests};
 use tokio::{
-    sync::{mpsc, oneshot, Mutex}, //
+    sync::{mpsc, oneshot, watch, Mutex}, //
     time::timeout,
 };
 use tokio_tungstenite::connect_async;
 
-use crate::{base_client::subscriptions::Subscriptions, prelude::*};
+use crate::{base_client::subscriptions::Subscriptions, error::RequestContext, prelude::*};
 // "wss://api.exchange.cryptomkt.com/api/3/ws/public"
 
 mod requests;
@@ -17,51 +17,71 @@ struct WebsocketClient {
     writer: Mutex<Writer>,
 }
 struct InnerClient {
-    private_key: hmac::Key,
-    public_key: String,
+    auth: Auth,
     url: String,
     requests: Requests,
     subscriptions: Subscriptions,
     ws: WebsocketClient,
     is_auth: Mutex<bool>,
+    // counts the reconnections of the websocket.
+    reconnections: watch::Sender<u64>,
 }
 #[derive(Clone)]
 pub(crate) struct BaseClient(Arc<InnerClient>);
 
 impl BaseClient {
-    pub async fn new(priv_k: &str, pub_k: &str, url: &str) -> Result<Self> {
-        let private_key = hmac::Key::new(hmac::HMAC_SHA256, priv_k.as_bytes());
+    pub async fn new(auth: Auth, url: &str) -> Result<Self> {
         log::debug!("Connecting to cryptomkt's websocket API.");
-        let (ws, _) = connect_async(url).await?;
+        let (ws, res) = connect_async(url).await?;
+        observe_date(&res);
 
         let (writer, reader) = ws.split();
         let writer = Mutex::new(writer);
         let reader = Mutex::new(reader);
 
         let client = Self(Arc::new(InnerClient {
-            private_key,
-            public_key: pub_k.into(),
+            auth,
             url: url.into(),
             requests: Requests::default(),
             subscriptions: Subscriptions::default(),
             ws: WebsocketClient { reader, writer },
             is_auth: Mutex::default(),
+            reconnections: watch::channel(0).0,
         }));
         client.clone().run_forever().await;
         Ok(client)
     }
+    /// The account the client is authenticated as.
+    pub fn account(&self) -> &crate::api::Account {
+        &self.0.auth.account
+    }
+
+    /// Notifies of the reconnections of the websocket. Subscriptions
+    /// are not restored by a reconnection.
+    pub fn reconnections(&self) -> watch::Receiver<u64> {
+        self.0.reconnections.subscribe()
+    }
+
     pub async fn is_auth(&self) -> bool {
         *self.0.is_auth.lock().await
     }
     pub async fn emit(&self, data: &impl Serialize) -> Result {
         let mut writer = self.0.ws.writer.lock().await;
-        let json = serde_json::to_string(data).unwrap();
+        let json = serde_json::to_string(data)?;
         writer.send(json.into()).await?;
         Ok(())
     }
 
     pub async fn authenticate(&self) -> Result<()> {
-        let request = Request::login(&self.0.private_key, &self.0.public_key);
+        let time_sync = crate::time_sync();
+        let timestamp = time_sync.timestamp_millis();
+        let window = time_sync.window();
+        let mut payload = timestamp.to_string();
+        if let Some(window) = window {
+            payload += &window.to_string();
+        }
+        let signature = self.0.auth.sign(payload.as_bytes()).await?;
+        let request = Request::login(&self.0.auth.api_key, timestamp, window, signature);
         let res = self.request(&request).await?;
         match res {
             | Response::Error(error) => Err(Error::from(error)),
@@ -73,21 +93,104 @@ impl BaseClient {
         }
     }
 
+    // describes the request for errors.
+    fn context(&self, req: &Request<'_>) -> RequestContext {
+        RequestContext {
+            method: req.method_name(),
+            id: req.id(),
+            endpoint: self.0.url.clone(),
+            client_order_id: req.client_order_id().map(Into::into),
+        }
+    }
+
+    /// Sends a request and waits for its response. Errors are
+    /// returned with the context of the request.
     pub async fn request(&self, req: &Request<'_>) -> Result<Response> {
+        self.send_request(req)
+            .await
+            .map(|reply| reply.response)
+            .map_err(|err| err.with_context(self.context(req)))
+    }
+
+    async fn send_request(&self, req: &Request<'_>) -> Result<Reply> {
         let (tx, rx) = oneshot::channel();
         let id = req.id();
-        self.0.requests.insert(id, tx).await;
+        self.0.requests.insert(id, Pending::Response(tx)).await;
         self.emit(req).await?;
         if let Ok(res) = timeout(crate::TIMEMOUT, rx).await {
-            match res? {
+            let reply = res??;
+            match reply.response {
                 | Response::Error(error) => Err(Error::from(error)),
-                | res => Ok(res),
+                | _ => Ok(reply),
+            }
+        } else {
+            Err(Error::Timeout)
+        }
+    }
+
+    /// Like `request`, but the response is returned as raw json.
+    /// On success, the `result` field of the response is returned.
+    pub async fn request_raw(&self, req: &Request<'_>) -> Result<serde_json::Value> {
+        self.send_request_raw(req)
+            .await
+            .map_err(|err| err.with_context(self.context(req)))
+    }
+
+    async fn send_request_raw(&self, req: &Request<'_>) -> Result<serde_json::Value> {
+        let (tx, rx) = oneshot::channel();
+        self.0.requests.insert(req.id(), Pending::Raw(tx)).await;
+        self.emit(req).await?;
+        if let Ok(res) = timeout(crate::TIMEMOUT, rx).await {
+            let mut json = res??;
+            if json.get("error").is_some() {
+                let error: crate::api::Error = serde_json::from_value(json)?;
+                Err(Error::from(error))
+            } else {
+                Ok(json["result"].take())
             }
         } else {
             Err(Error::Timeout)
         }
     }
 
+    /// Sends a raw request for the method given, going through
+    /// the same id tracking and error mapping as typed requests.
+    pub async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        let request = Request::raw(method, params);
+        self.request_raw(&request).await
+    }
+
+    /// Subscribes to a channel with arbitrary params. Notifications are
+    /// routed by their `ch` or `method` field and streamed as the json
+    /// they were received as.
+    pub async fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Receiver<serde_json::Value>, serde_json::Value)> {
+        let (tx, rx) = mpsc::channel(8);
+        self.0.subscriptions.insert_raw(channel, tx).await;
+        let request = Request::subscribe_raw(channel, params);
+        let res = self.request_raw(&request).await?;
+        Ok((rx, res))
+    }
+
+    /// Sends a request and decodes the response with `f`, failing with
+    /// `Error::UnexpectedResponse` if the response has a different type.
+    pub async fn request_as<T>(
+        &self,
+        req: &Request<'_>,
+        f: impl FnOnce(Response) -> Result<T, Response>,
+    ) -> Result<T> {
+        let context = || self.context(req);
+        let Reply { response, msg } = self
+            .send_request(req)
+            .await
+            .map_err(|err| err.with_context(context()))?;
+        f(response)
+            .map_err(|_| Error::unexpected::<T>(req.method_name(), &msg).with_context(context()))
+    }
+
     async fn register_subs<F>(&self, method: &str, f: F)
     where
         F: subscriptions::Closure,
@@ -95,38 +198,31 @@ impl BaseClient {
         self.0.subscriptions.insert(method, Box::new(f)).await;
     }
 
-    pub async fn subscribe<T>(
-        &self,
-        method: &str,
-        tx: mpsc::Sender<T>,
-        f: fn(Response) -> Result<T, Response>,
-    ) where
+    pub async fn subscribe<T, F>(&self, method: &str, tx: mpsc::Sender<T>, f: F)
+    where
         T: Send + Sync + 'static,
+        F: Fn(Response) -> Result<T, Response> + Send + Sync + 'static,
     {
         self.register_subs(method, move |res| {
             let tx = tx.clone();
+            let t = f(res);
             Box::pin(async move {
-                let t = f(res)?;
-                (tx).send(t).await.ok();
+                (tx).send(t?).await.ok();
                 Ok(())
             }) as subscriptions::ClosureOutput
         })
         .await;
     }
-    pub async fn subscribe_vec<T>(
-        &self,
-        method: &'static str,
-        tx: Sender<T>,
-        f: fn(Response) -> Result<Vec<T>, Response>,
-    ) where
+    pub async fn subscribe_vec<T, F>(&self, method: &'static str, tx: Sender<T>, f: F)
+    where
         T: Send + Sync + 'static,
+        F: Fn(Response) -> Result<Vec<T>, Response> + Send + Sync + 'static,
     {
         self.register_subs(method, move |res| {
             let tx = tx.clone();
-
+            let objects = f(res);
             Box::pin(async move {
-                let objects = f(res)?;
-                for obj in objects {
+                for obj in objects? {
                     tx.send(obj).await.ok();
                 }
                 Ok(())
@@ -135,6 +231,19 @@ impl BaseClient {
         .await;
     }
 
+    /// Registers a handler called with the market data of the channel,
+    /// borrowed from the message it was decoded from.
+    #[cfg(feature = "zero-copy")]
+    pub async fn subscribe_borrowed<F>(&self, channel: &str, f: F)
+    where
+        F: Fn(crate::api::MarketData<'_>) + Send + Sync + 'static,
+    {
+        self.0
+            .subscriptions
+            .insert_handler(channel, Box::new(f))
+            .await;
+    }
+
     pub async fn unsubscribe(&self, method: &'static str) {
         self.0.subscriptions.remove(method).await;
     }
@@ -150,21 +259,37 @@ impl BaseClient {
         if msg.is_empty() {
             return Ok(());
         }
-        let res: Response = serde_json::from_str(&msg)?;
-
-        if let Err(res) = self.0.requests.intercept(res).await {
-            if let Err(res) = self.0.subscriptions.intercept(res).await {
-                error!(
-                    "unhandeled message: {}",
-                    serde_json::to_string(&res).unwrap()
-                );
+        #[cfg(feature = "zero-copy")]
+        if self.0.subscriptions.intercept_market_data(&msg).await {
+            return Ok(());
+        }
+        let res = match Response::decode(&msg) {
+            | Ok(res) => res,
+            | Err(err) => {
+                // a reply to a request is passed to the task awaiting it.
+                if let Err(err) = self.0.requests.intercept_undecoded(&msg, err).await {
+                    error!("could not decode message {}: {}", msg, err);
+                }
+                return Ok(());
+            }
+        };
+        if let Response::Channel(channel) = &res {
+            if let Some(timestamp) = channel.timestamp() {
+                crate::time_sync().observe(timestamp);
+            }
+        }
+
+        if let Err(res) = self.0.requests.intercept(res, &msg).await {
+            if let Err(res) = self.0.subscriptions.intercept(res, &msg).await {
+                error!("unhandeled message: {:?}", res);
             }
         }
         Ok(())
     }
 
     async fn reconnect(&self) -> Result {
-        let (ws, _) = tokio_tungstenite::connect_async(&self.0.url).await?;
+        let (ws, res) = tokio_tungstenite::connect_async(&self.0.url).await?;
+        observe_date(&res);
         let (writer, reader) = ws.split();
         let mut w = self.0.ws.writer.lock().await;
         let mut r = self.0.ws.reader.lock().await;
@@ -174,6 +299,8 @@ impl BaseClient {
     }
     async fn run_forever(self) {
         spawn(async move {
+            // the number of reconnections that failed in a row.
+            let mut failures = 0;
             loop {
                 match self.recv().await {
                     | Ok(()) => (),
@@ -182,7 +309,11 @@ impl BaseClient {
                         info!("reconnecting to cryptomkt.");
                         if let Err(err) = self.reconnect().await {
                             error!("reconnection error: {}", err);
+                            tokio::time::sleep(reconnection_delay(failures)).await;
+                            failures += 1;
+                            continue;
                         }
+                        failures = 0;
                         if self.is_auth().await {
                             self.authenticate()
                                 .await
@@ -191,9 +322,34 @@ impl BaseClient {
                                 })
                                 .ok();
                         }
+                        self.0.reconnections.send_modify(|count| *count += 1);
                     }
                 }
             }
         });
     }
 }
+
+// Doubles the delay after each failed reconnection, up to 32 seconds.
+fn reconnection_delay(failures: u32) -> Duration {
+    Duration::from_secs(1 << failures.min(5))
+}
+
+// The `Date` header of the handshake is a coarse sample of the server time.
+fn observe_date(res: &tokio_tungstenite::tungstenite::handshake::client::Response) {
+    let date = res
+        .headers()
+        .get("date")
+        .and_then(|date| date.to_str().ok())
+        .and_then(|date| DateTime::parse_from_rfc2822(date).ok());
+    if let Some(date) = date {
+        crate::time_sync().observe(date.with_timezone(&Utc));
+    }
+}
+
+#[test]
+fn reconnection_backoff() {
+    assert_eq!(reconnection_delay(0), Duration::from_secs(1));
+    assert_eq!(reconnection_delay(3), Duration::from_secs(8));
+    assert_eq!(reconnection_delay(100), Duration::from_secs(32));
+}
@@ -1,16 +1,29 @@
 use tokio::sync::{oneshot::Sender, Mutex};
 
 use crate::prelude::*;
+
+// A response along with the message it was decoded from.
+pub struct Reply {
+    pub response: Response,
+    pub msg: String,
+}
+
+// An awaiting task, either expecting a decoded response or the raw JSON.
+pub enum Pending {
+    Response(Sender<Result<Reply>>),
+    Raw(Sender<Result<serde_json::Value>>),
+}
+
 #[derive(Default)]
-pub struct Requests(Mutex<HashMap<i64, (i64, Sender<Response>)>>);
+pub struct Requests(Mutex<HashMap<i64, (i64, Pending)>>);
 
 impl Requests {
     // It is used when making a request to register a response.
-    pub async fn insert(&self, id: i64, tx: Sender<Response>) {
+    pub async fn insert(&self, id: i64, tx: Pending) {
         let time = chrono::Utc::now().timestamp();
         let mut hashmap = self.0.lock().await;
         hashmap.insert(id, (time, tx));
-        if hashmap.len() != 0 {
+        if !hashmap.is_empty() {
             drop(hashmap);
             self.clear().await;
         }
@@ -25,22 +38,79 @@ impl Requests {
 
     // given a API response, it will try to send it to an awaiting task if there is one.
     // if no tasks are waiting it will return the response so it can be streamed.
-    pub async fn intercept(&self, response: Response) -> Result<(), Response> {
+    // `msg` is the text the response was decoded from, used by raw requests.
+    pub async fn intercept(&self, response: Response, msg: &str) -> Result<(), Response> {
         let id = response.id();
         if let Some(id) = id {
-            self.send_response(id, response).await
+            self.send_response(id, response, msg).await
         } else {
             Err(response)
         }
     }
 
-    async fn send_response(&self, id: i64, response: Response) -> Result<(), Response> {
+    async fn send_response(&self, id: i64, response: Response, msg: &str) -> Result<(), Response> {
         let mut hashmap = self.0.lock().await;
-        if let Some((_, (_, tx))) = hashmap.remove_entry(&id) {
-            tx.send(response)?;
-            Ok(())
-        } else {
-            Err(response)
+        match hashmap.remove_entry(&id) {
+            | Some((_, (_, Pending::Response(tx)))) => {
+                let reply = Reply {
+                    response,
+                    msg: msg.into(),
+                };
+                tx.send(Ok(reply)).ok();
+                Ok(())
+            }
+            | Some((_, (_, Pending::Raw(tx)))) => match serde_json::from_str(msg) {
+                | Ok(json) => {
+                    tx.send(Ok(json)).ok();
+                    Ok(())
+                }
+                | Err(_) => Err(response),
+            },
+            | None => Err(response),
         }
     }
+
+    // sends a message that could not be decoded to the task awaiting it, if
+    // any. Returns the error back if no task is awaiting the message.
+    pub async fn intercept_undecoded(
+        &self,
+        msg: &str,
+        error: serde_json::Error,
+    ) -> Result<(), serde_json::Error> {
+        let json: serde_json::Value = match serde_json::from_str(msg) {
+            | Ok(json) => json,
+            | Err(_) => return Err(error),
+        };
+        let id = match json["id"].as_i64() {
+            | Some(id) => id,
+            | None => return Err(error),
+        };
+        let mut hashmap = self.0.lock().await;
+        match hashmap.remove(&id) {
+            | Some((_, Pending::Response(tx))) => {
+                tx.send(Err(Error::decoding(msg, error))).ok();
+                Ok(())
+            }
+            | Some((_, Pending::Raw(tx))) => {
+                tx.send(Ok(json)).ok();
+                Ok(())
+            }
+            | None => Err(error),
+        }
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn undecoded_response() {
+    let requests = Requests::default();
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    requests.insert(7, Pending::Response(tx)).await;
+    let msg = r#"{"id": 7, "ch": 1}"#;
+    let error = Response::decode(msg).unwrap_err();
+    assert!(requests.intercept_undecoded(msg, error).await.is_ok());
+    match rx.await.unwrap() {
+        | Err(Error::Decoding { message, .. }) => assert_eq!(message, msg),
+        | _ => panic!("the message was not routed as an error"),
+    }
 }
@@ -4,28 +4,51 @@ use log::error;
 use std::collections::HashMap;
 use std::future::Future;
 use std::pin::Pin;
-use tokio::sync::Mutex;
+use tokio::sync::{mpsc, Mutex};
 
 pub(crate) type ClosureOutput =
     Pin<Box<dyn Future<Output = Result<(), Response>> + Send + Sync + 'static>>;
 pub(crate) trait Closure: Fn(Response) -> ClosureOutput + Send + Sync + 'static {}
 impl<F: Fn(Response) -> ClosureOutput + Send + Sync + 'static> Closure for F {}
 
+#[cfg(feature = "zero-copy")]
+pub(crate) type Handler = Box<dyn Fn(crate::api::MarketData<'_>) + Send + Sync + 'static>;
+
 #[derive(Default)]
-pub struct Subscriptions(Mutex<HashMap<String, Box<dyn Closure>>>);
+pub struct Subscriptions(
+    Mutex<HashMap<String, Box<dyn Closure>>>,
+    Mutex<HashMap<String, mpsc::Sender<serde_json::Value>>>,
+    #[cfg(feature = "zero-copy")] Mutex<HashMap<String, Handler>>,
+);
 
 impl Subscriptions {
     pub(crate) async fn insert<F: Closure>(&self, method: &str, f: F) {
         self.0.lock().await.insert(method.into(), Box::new(f));
     }
 
-    pub async fn intercept(&self, response: Response) -> Result<(), Response> {
-        let method = response.method();
-        if let Some(method) = method {
-            self.send(method, response).await
-        } else {
-            Err(response)
+    /// Registers a channel whose notifications are sent as the json
+    /// they were received as.
+    pub(crate) async fn insert_raw(&self, channel: &str, tx: mpsc::Sender<serde_json::Value>) {
+        self.1.lock().await.insert(channel.into(), tx);
+    }
+
+    // `msg` is the text the response was decoded from, used by raw subscriptions.
+    pub async fn intercept(&self, response: Response, msg: &str) -> Result<(), Response> {
+        let method = match response.method() {
+            | Some(method) => method,
+            | None => return Err(response),
+        };
+        let raw = self.1.lock().await.get(&method).cloned();
+        if let Some(tx) = raw {
+            return match serde_json::from_str(msg) {
+                | Ok(json) => {
+                    tx.send(json).await.ok();
+                    Ok(())
+                }
+                | Err(_) => Err(response),
+            };
         }
+        self.send(method, response).await
     }
 
     async fn send(&self, method: String, response: Response) -> Result<(), Response> {
@@ -43,7 +66,52 @@ impl Subscriptions {
         }
     }
 
+    #[cfg(feature = "zero-copy")]
+    pub(crate) async fn insert_handler(&self, channel: &str, f: Handler) {
+        self.2.lock().await.insert(channel.into(), f);
+    }
+
+    /// Passes market data to the handler of its channel without converting
+    /// it to an owned `Response`. Returns whether the message was handled.
+    #[cfg(feature = "zero-copy")]
+    pub async fn intercept_market_data(&self, msg: &str) -> bool {
+        let handlers = self.2.lock().await;
+        if handlers.is_empty() {
+            return false;
+        }
+        let market = match crate::api::MarketData::decode(msg) {
+            | Ok(Some(market)) => market,
+            | _ => return false,
+        };
+        match handlers.get(market.ch) {
+            | Some(f) => {
+                if let Some(timestamp) = market.timestamp() {
+                    crate::time_sync().observe(timestamp);
+                }
+                f(market);
+                true
+            }
+            | None => false,
+        }
+    }
+
     pub async fn remove(&self, method: &'static str) {
         self.0.lock().await.remove(method);
+        self.1.lock().await.remove(method);
+        #[cfg(feature = "zero-copy")]
+        self.2.lock().await.remove(method);
     }
 }
+
+#[cfg(test)]
+#[tokio::test]
+async fn raw_subscription() {
+    let subscriptions = Subscriptions::default();
+    let (tx, mut rx) = mpsc::channel(1);
+    subscriptions.insert_raw("ticker/1s", tx).await;
+    // fields the typed model does not know of are kept.
+    let msg = r#"{"ch":"ticker/1s","data":{},"extra":1}"#;
+    let response = Response::decode(msg).unwrap();
+    assert!(subscriptions.intercept(response, msg).await.is_ok());
+    assert_eq!(rx.recv().await.unwrap()["extra"], 1);
+}
@@ -0,0 +1,78 @@
+//! # Blocking clients
+//! Synchronous wrappers over the websocket clients, enabled with the
+//! `blocking` feature. Each client owns a private runtime which drives
+//! the connection in the background, so they must not be used from
+//! within an async context.
+//!
+//! ```no_run
+//! # fn doc() -> Result<(), cryptomarket::Error> {
+//! use cryptomarket::blocking::WalletClient;
+//!
+//! let client = WalletClient::new("private_key", "public_key")?;
+//! for balance in client.wallet_balance()? {
+//!     println!("{}: {}", balance.currency, balance.available);
+//! }
+//! # Ok(()) }
+//! ```
+use crate::prelude::*;
+use tokio::runtime::{Builder, Runtime};
+
+pub use public_client::PublicClient;
+pub use trading_client::TradingClient;
+pub use wallet_client::WalletClient;
+
+mod public_client;
+mod trading_client;
+mod wallet_client;
+
+/// An iterator over the events of a subscription. Iteration blocks
+/// until the next event arrives, and ends when the subscription is closed.
+pub struct Subscription<T> {
+    rx: Receiver<T>,
+    _runtime: Arc<Runtime>,
+}
+
+impl<T> Subscription<T> {
+    fn new(rx: Receiver<T>, runtime: &Arc<Runtime>) -> Self {
+        Self {
+            rx,
+            _runtime: runtime.clone(),
+        }
+    }
+}
+
+impl<T> Iterator for Subscription<T> {
+    type Item = T;
+    fn next(&mut self) -> Option<T> {
+        self.rx.blocking_recv()
+    }
+}
+
+fn runtime() -> Result<Arc<Runtime>> {
+    let runtime = Builder::new_multi_thread()
+        .worker_threads(1)
+        .enable_all()
+        .build()?;
+    Ok(Arc::new(runtime))
+}
+
+#[test]
+fn subscription_iteration() {
+    let runtime = runtime().unwrap();
+    let (tx, rx) = tokio::sync::mpsc::channel(4);
+    let subscription = Subscription::new(rx, &runtime);
+    // the subscription keeps the runtime alive.
+    assert_eq!(Arc::strong_count(&runtime), 2);
+    runtime.spawn(async move {
+        for i in 0..3 {
+            tx.send(i).await.unwrap();
+        }
+    });
+    // iteration ends when the sender is dropped.
+    assert_eq!(subscription.collect::<Vec<_>>(), [0, 1, 2]);
+    assert_eq!(Arc::strong_count(&runtime), 1);
+
+    let (tx, rx) = tokio::sync::mpsc::channel::<i32>(4);
+    drop(Subscription::new(rx, &runtime));
+    assert!(tx.is_closed());
+}
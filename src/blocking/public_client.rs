@@ -0,0 +1,159 @@
+use super::*;
+use crate::api::{
+    CandleMap, CandlePeriod, Channel, Event, Orderbook, OrderbookDepth, OrderbookMap,
+    OrderbookSpeed, PriceTickerMap, Subscriptions as Subs, Ticker, TickerSpeed, TopOrderMap,
+    TradeMap,
+};
+use tokio::runtime::Runtime;
+
+/// A blocking version of [`crate::PublicClient`].
+#[derive(Clone)]
+pub struct PublicClient {
+    client: crate::PublicClient,
+    runtime: Arc<Runtime>,
+}
+
+impl PublicClient {
+    pub fn new(private_key: &str, public_key: &str) -> Result<Self> {
+        Self::with_credentials(Credentials::new(private_key, public_key))
+    }
+
+    pub fn with_credentials(credentials: Credentials) -> Result<Self> {
+        let runtime = runtime()?;
+        let client = runtime.block_on(crate::PublicClient::with_credentials(credentials))?;
+        Ok(Self { client, runtime })
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub fn with_signer(api_key: &str, signer: impl Signer + 'static) -> Result<Self> {
+        let runtime = runtime()?;
+        let client = runtime.block_on(crate::PublicClient::with_signer(api_key, signer))?;
+        Ok(Self { client, runtime })
+    }
+
+    pub fn authenticate(&self) -> Result<()> {
+        self.runtime.block_on(self.client.authenticate())
+    }
+
+    /// Sends a request for any method of the API. Returns the `result`
+    /// field of the response.
+    pub fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        self.runtime.block_on(self.client.call(method, params))
+    }
+
+    /// Subscribes to any channel of the API with the params given.
+    pub fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Subscription<serde_json::Value>, serde_json::Value)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_raw(channel, params))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscriptions(&self, channel: Channel) -> Result<Response> {
+        self.runtime.block_on(self.client.subscriptions(channel))
+    }
+
+    pub fn subscribe_trades(
+        &self,
+        symbols: &[impl AsRef<str>],
+    ) -> Result<(Subscription<Event<TradeMap>>, Subs)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_trades(symbols))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_full_orderbook(
+        &self,
+        symbols: &[impl AsRef<str>],
+    ) -> Result<(Subscription<Event<OrderbookMap>>, Subs)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_full_orderbook(symbols))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_partial_orderbook(
+        &self,
+        symbols: &[impl AsRef<str>],
+        depth: OrderbookDepth,
+        speed: OrderbookSpeed,
+    ) -> Result<(Subscription<HashMap<String, Orderbook>>, Subs)> {
+        let (rx, res) = self.runtime.block_on(
+            self.client
+                .subscribe_partial_orderbook(symbols, depth, speed),
+        )?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_ticker(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Subscription<Ticker>, Subs)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_ticker(symbols, speed))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_price_ticker(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Subscription<PriceTickerMap>, Subs)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_price_ticker(symbols, speed))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_ticker_batch(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Subscription<Ticker>, Subs)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_ticker_batch(symbols, speed))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_price_ticker_batch(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Subscription<PriceTickerMap>, Subs)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_price_ticker_batch(symbols, speed))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_candles(
+        &self,
+        symbols: &[impl AsRef<str>],
+        period: CandlePeriod,
+        limit: i32,
+    ) -> Result<(Subscription<Event<CandleMap>>, Subs)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_candles(symbols, period, limit))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_top_order(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: OrderbookSpeed,
+    ) -> Result<(Subscription<TopOrderMap>, Subs)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_top_order(symbols, speed))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+}
@@ -0,0 +1,91 @@
+use super::*;
+use crate::api::{Balance, Fee, Order};
+use crate::NewOrder;
+use tokio::runtime::Runtime;
+
+/// A blocking version of [`crate::TradingClient`].
+#[derive(Clone)]
+pub struct TradingClient {
+    client: crate::TradingClient,
+    runtime: Arc<Runtime>,
+}
+
+impl TradingClient {
+    pub fn new(priv_key: &str, pub_key: &str) -> Result<Self> {
+        Self::with_credentials(Credentials::new(priv_key, pub_key))
+    }
+
+    pub fn with_credentials(credentials: Credentials) -> Result<Self> {
+        let runtime = runtime()?;
+        let client = runtime.block_on(crate::TradingClient::with_credentials(credentials))?;
+        Ok(Self { client, runtime })
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub fn with_signer(api_key: &str, signer: impl Signer + 'static) -> Result<Self> {
+        let runtime = runtime()?;
+        let client = runtime.block_on(crate::TradingClient::with_signer(api_key, signer))?;
+        Ok(Self { client, runtime })
+    }
+
+    /// Sends a request for any method of the API. Returns the `result`
+    /// field of the response.
+    pub fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        self.runtime.block_on(self.client.call(method, params))
+    }
+
+    /// Subscribes to any channel of the API with the params given.
+    pub fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Subscription<serde_json::Value>, serde_json::Value)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_raw(channel, params))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    pub fn subscribe_orders(&self) -> Result<Subscription<Order>> {
+        let rx = self.runtime.block_on(self.client.subscribe_orders())?;
+        Ok(Subscription::new(rx, &self.runtime))
+    }
+
+    pub fn unsubscribe_orders(&self) -> Result<()> {
+        self.runtime.block_on(self.client.unsubscribe_orders())
+    }
+    /// returns the orders that are currently active.
+    pub fn get_orders(&self) -> Result<Vec<Order>> {
+        self.runtime.block_on(self.client.get_orders())
+    }
+    /// places a new order on the exchange.
+    pub fn place_order(&self, order: NewOrder) -> Result<Order> {
+        self.runtime.block_on(self.client.place_order(order))
+    }
+    /// cancels all orders issed by the client.
+    pub fn cancel_orders(&self) -> Result<Vec<Order>> {
+        self.runtime.block_on(self.client.cancel_orders())
+    }
+    /// cancels a specific order using the `client_order_id`.
+    pub fn cancel_order(&self, client_order_id: &str) -> Result<Order> {
+        self.runtime
+            .block_on(self.client.cancel_order(client_order_id))
+    }
+
+    pub fn get_balance(&self) -> Result<Vec<Balance>> {
+        self.runtime.block_on(self.client.get_balance())
+    }
+    /// get the fees for all markets.
+    pub fn get_fees(&self) -> Result<Vec<Fee>> {
+        self.runtime.block_on(self.client.get_fees())
+    }
+    /// get the fee for a specific market
+    pub fn get_fee(&self, symbol: impl AsRef<str>) -> Result<Fee> {
+        self.runtime.block_on(self.client.get_fee(symbol))
+    }
+
+    pub fn replace_order(&self, order_id: &str, quantity: Number, price: Number) -> Result<Order> {
+        self.runtime
+            .block_on(self.client.replace_order(order_id, quantity, price))
+    }
+}
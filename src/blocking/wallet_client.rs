@@ -0,0 +1,82 @@
+use super::*;
+use crate::api::{Amount, Balance, Transaction};
+use tokio::runtime::Runtime;
+
+/// A blocking version of [`crate::WalletClient`].
+#[derive(Clone)]
+pub struct WalletClient {
+    client: crate::WalletClient,
+    runtime: Arc<Runtime>,
+}
+
+impl WalletClient {
+    pub fn new(priv_key: &str, pub_key: &str) -> Result<Self> {
+        Self::with_credentials(Credentials::new(priv_key, pub_key))
+    }
+
+    pub fn with_credentials(credentials: Credentials) -> Result<Self> {
+        let runtime = runtime()?;
+        let client = runtime.block_on(crate::WalletClient::with_credentials(credentials))?;
+        Ok(Self { client, runtime })
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub fn with_signer(api_key: &str, signer: impl Signer + 'static) -> Result<Self> {
+        let runtime = runtime()?;
+        let client = runtime.block_on(crate::WalletClient::with_signer(api_key, signer))?;
+        Ok(Self { client, runtime })
+    }
+
+    pub fn is_auth(&self) -> bool {
+        self.runtime.block_on(self.client.is_auth())
+    }
+
+    /// Sends a request for any method of the API. Returns the `result`
+    /// field of the response.
+    pub fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        self.runtime.block_on(self.client.call(method, params))
+    }
+
+    /// Subscribes to any channel of the API with the params given.
+    pub fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Subscription<serde_json::Value>, serde_json::Value)> {
+        let (rx, res) = self
+            .runtime
+            .block_on(self.client.subscribe_raw(channel, params))?;
+        Ok((Subscription::new(rx, &self.runtime), res))
+    }
+
+    /// Returns the available balance for the specific currency
+    pub fn currency_balance(&self, currency: impl AsRef<str>) -> Result<Amount> {
+        self.runtime
+            .block_on(self.client.currency_balance(currency))
+    }
+    /// Used to get the current wallet balance for all currencies.
+    pub fn wallet_balance(&self) -> Result<Vec<Balance>> {
+        self.runtime.block_on(self.client.wallet_balance())
+    }
+    /// Used to subscribe to changes to the wallet balance for all currencies.
+    pub fn subscribe_wallet_balances(&self) -> Result<Subscription<Balance>> {
+        let rx = self
+            .runtime
+            .block_on(self.client.subscribe_wallet_balances())?;
+        Ok(Subscription::new(rx, &self.runtime))
+    }
+
+    /// Used to subsctibe to transactions.
+    pub fn subscribe_transactions(&self) -> Result<Subscription<Transaction>> {
+        let rx = self
+            .runtime
+            .block_on(self.client.subscribe_transactions())?;
+        Ok(Subscription::new(rx, &self.runtime))
+    }
+
+    /// Used to unsubscribe to changes to the wallet balance for all currencies.
+    pub fn unsubscribe_wallet_balances(&self) -> Result<bool> {
+        self.runtime
+            .block_on(self.client.unsubscribe_wallet_balances())
+    }
+}
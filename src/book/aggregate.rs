@@ -0,0 +1,242 @@
+use super::local::Price;
+use super::{BookAnalytics, LevelChange, LocalOrderBook};
+use crate::api::{PriceLevel, Side, SymbolInfo};
+use crate::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, Default)]
+struct Bucket {
+    quantity: Number,
+    // the number of levels in the bucket, so empty buckets are removed
+    // regardless of rounding errors in the quantity.
+    levels: usize,
+}
+
+/// A view of a local orderbook with its levels grouped in buckets of a
+/// fixed price increment. Asks are grouped in the bucket above their
+/// price, and bids in the bucket below, so the view never shows a better
+/// price than the book.
+///
+/// The book the view is built from may already contain updates whose
+/// events were not received yet. Those changes are skipped by `apply`,
+/// using the sequence number of the update.
+/// ```no_run
+/// # async fn doc(client: cryptomarket::PublicClient) -> Result<(), cryptomarket::Error> {
+/// use cryptomarket::book::{AggregatedBook, BookEvent, BookManager};
+///
+/// let (manager, mut events) = BookManager::new(client, &["BTCCLP"]).await?;
+/// let increment = 1000.into();
+/// let mut view: Option<AggregatedBook> = None;
+/// while let Some(event) = events.recv().await {
+///     match event {
+///         BookEvent::Update {
+///             sequence, changes, ..
+///         } => {
+///             if let Some(view) = &mut view {
+///                 view.apply(sequence, &changes);
+///             }
+///         }
+///         BookEvent::Snapshot { .. } | BookEvent::Lagged { .. } => {
+///             view = manager
+///                 .with_book("BTCCLP", |book| AggregatedBook::new(book, increment))
+///                 .await
+///                 .flatten();
+///         }
+///         _ => view = None,
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct AggregatedBook {
+    increment: Number,
+    // the sequence number of the last update applied.
+    sequence: i64,
+    asks: BTreeMap<Price, Bucket>,
+    bids: BTreeMap<Price, Bucket>,
+}
+
+impl AggregatedBook {
+    /// Groups the levels of the book in buckets of `increment`. Returns
+    /// `None` if the increment is not positive.
+    pub fn new(book: &LocalOrderBook, increment: Number) -> Option<Self> {
+        if increment.partial_cmp(&Number::default()) != Some(Ordering::Greater) {
+            return None;
+        }
+        let mut aggregated = AggregatedBook {
+            increment,
+            sequence: book.sequence(),
+            asks: BTreeMap::new(),
+            bids: BTreeMap::new(),
+        };
+        aggregated.reset(book);
+        Some(aggregated)
+    }
+
+    /// Groups the levels of the book in buckets of `ticks` times the tick
+    /// size of the symbol. Returns `None` if the increment is not positive.
+    pub fn with_ticks(book: &LocalOrderBook, info: &SymbolInfo, ticks: u32) -> Option<Self> {
+        Self::new(book, info.tick_size * Number::from(ticks))
+    }
+
+    pub fn increment(&self) -> Number {
+        self.increment
+    }
+
+    /// Rebuilds the buckets from the book, after a new snapshot.
+    pub fn reset(&mut self, book: &LocalOrderBook) {
+        self.sequence = book.sequence();
+        self.asks.clear();
+        self.bids.clear();
+        let asks = book.asks().map(|level| (Side::Sell, level));
+        let bids = book.bids().map(|level| (Side::Buy, level));
+        for (side, level) in asks.chain(bids) {
+            self.add(side, level.price, level.quantity, Number::default());
+        }
+    }
+
+    /// Applies the changes of the update of the book with the sequence
+    /// number given. Updates the view already contains are ignored.
+    pub fn apply(&mut self, sequence: i64, changes: &[LevelChange]) {
+        if sequence <= self.sequence {
+            return;
+        }
+        self.sequence = sequence;
+        for change in changes {
+            self.add(change.side, change.price, change.quantity, change.previous);
+        }
+    }
+
+    fn add(&mut self, side: Side, price: Number, quantity: Number, previous: Number) {
+        let zero = Number::default();
+        let steps = price / self.increment;
+        // prices that are a multiple of the increment may be divided with
+        // a rounding error, such as 0.3 / 0.1 = 2.9999999999999996.
+        let nearest = steps.round();
+        let exact = (steps - nearest).abs() < Number::from(1) / Number::from(1_000_000_000);
+        let (buckets, steps) = match side {
+            | Side::Sell if exact => (&mut self.asks, nearest),
+            | Side::Sell => (&mut self.asks, steps.ceil()),
+            | Side::Buy if exact => (&mut self.bids, nearest),
+            | Side::Buy => (&mut self.bids, steps.floor()),
+        };
+        let key = Price(steps * self.increment);
+        let bucket = buckets.entry(key).or_default();
+        bucket.quantity += quantity - previous;
+        match (previous == zero, quantity == zero) {
+            | (true, false) => bucket.levels += 1,
+            | (false, true) => bucket.levels = bucket.levels.saturating_sub(1),
+            | _ => (),
+        }
+        if bucket.levels == 0 {
+            buckets.remove(&key);
+        }
+    }
+
+    /// The buckets of the asks, from the lowest price to the highest.
+    pub fn asks(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks.iter().map(level)
+    }
+
+    /// The buckets of the bids, from the highest price to the lowest.
+    pub fn bids(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids.iter().rev().map(level)
+    }
+}
+
+fn level((price, bucket): (&Price, &Bucket)) -> PriceLevel {
+    PriceLevel {
+        price: price.0,
+        quantity: bucket.quantity,
+    }
+}
+
+impl BookAnalytics for AggregatedBook {
+    fn ask_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks()
+    }
+
+    fn bid_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids()
+    }
+}
+
+#[test]
+fn aggregate_levels() {
+    let n = |n: &str| n.parse::<Number>().unwrap();
+    let snapshot = super::local::book(
+        1,
+        &[("100.25", "1"), ("100.5", "2"), ("101.5", "4")],
+        &[("99.75", "3"), ("99.25", "1"), ("98.5", "2")],
+    );
+    let mut book = LocalOrderBook::from_snapshot("BTCCLP", &snapshot);
+    let mut view = AggregatedBook::new(&book, n("1")).unwrap();
+    let asks: Vec<_> = view
+        .asks()
+        .map(|level| (level.price, level.quantity))
+        .collect();
+    assert_eq!(asks, [(n("101"), n("3")), (n("102"), n("4"))]);
+    let bids: Vec<_> = view
+        .bids()
+        .map(|level| (level.price, level.quantity))
+        .collect();
+    assert_eq!(bids, [(n("99"), n("4")), (n("98"), n("2"))]);
+
+    let update = super::local::book(2, &[("100.25", "0"), ("100.5", "0.5")], &[("98.5", "0")]);
+    view.apply(2, &book.apply_update(&update).unwrap());
+    assert_eq!(view.best_ask().unwrap().quantity, n("0.5"));
+    assert_eq!(view.bids().count(), 1);
+
+    let update = super::local::book(3, &[("100.5", "0")], &[]);
+    view.apply(3, &book.apply_update(&update).unwrap());
+    assert_eq!(view.best_ask().unwrap().price, n("102"));
+
+    assert!(AggregatedBook::new(&book, n("0")).is_none());
+    assert!(AggregatedBook::new(&book, n("-1")).is_none());
+}
+
+#[test]
+fn skip_applied_updates() {
+    let n = |n: &str| n.parse::<Number>().unwrap();
+    let snapshot = super::local::book(1, &[("100.25", "1")], &[("99.75", "3")]);
+    let mut book = LocalOrderBook::from_snapshot("BTCCLP", &snapshot);
+    // the update is applied to the book before its event is received.
+    let update = super::local::book(2, &[("100.25", "0")], &[("99.75", "5")]);
+    let changes = book.apply_update(&update).unwrap();
+
+    let mut view = AggregatedBook::new(&book, n("1")).unwrap();
+    view.apply(2, &changes);
+    assert_eq!(view.asks().count(), 0);
+    assert_eq!(view.best_bid().unwrap().quantity, n("5"));
+
+    let update = super::local::book(3, &[], &[("99.75", "0")]);
+    view.apply(3, &book.apply_update(&update).unwrap());
+    assert_eq!(view.bids().count(), 0);
+}
+
+#[test]
+fn fractional_increments() {
+    let n = |n: &str| n.parse::<Number>().unwrap();
+    let prices = |levels: Vec<PriceLevel>| -> Vec<_> { levels.iter().map(|l| l.price).collect() };
+    let snapshot = super::local::book(
+        1,
+        &[("0.07", "1"), ("0.071", "1")],
+        &[("0.3", "1"), ("100.3", "1"), ("0.35", "1")],
+    );
+    let book = LocalOrderBook::from_snapshot("ETHBTC", &snapshot);
+
+    // exact multiples of the increment stay in their own bucket.
+    let tenths = AggregatedBook::new(&book, n("0.1")).unwrap();
+    let bucket = |steps: i32| Number::from(steps) * n("0.1");
+    assert_eq!(prices(tenths.bids().collect()), [bucket(1003), bucket(3)]);
+    assert_eq!(tenths.bids().last().unwrap().quantity, n("2"));
+
+    let hundredths = AggregatedBook::new(&book, n("0.01")).unwrap();
+    let bucket = |steps: i32| Number::from(steps) * n("0.01");
+    assert_eq!(prices(hundredths.asks().collect()), [bucket(7), bucket(8)]);
+    assert_eq!(
+        prices(hundredths.bids().collect()),
+        [bucket(10030), bucket(35), bucket(30)]
+    );
+}
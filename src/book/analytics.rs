@@ -0,0 +1,286 @@
+use crate::api::{Orderbook, PriceLevel, Side, TopOrder};
+use crate::prelude::*;
+
+use super::LocalOrderBook;
+
+/// Calculations over the levels of an orderbook. It is implemented for
+/// local books, for the partial books of the `orderbook/{depth}/{speed}`
+/// channels and for the top of book.
+/// ```
+/// # fn doc(book: cryptomarket::book::LocalOrderBook) {
+/// use cryptomarket::{book::BookAnalytics, Side};
+///
+/// if let (Some(spread), Some(vwap)) = (book.spread_bps(), book.vwap(Side::Buy, 1.into())) {
+///     println!("spread: {} bps, price to buy 1: {}", spread, vwap);
+/// }
+/// # }
+/// ```
+pub trait BookAnalytics {
+    /// The asks, from the lowest price to the highest.
+    fn ask_levels(&self) -> impl Iterator<Item = PriceLevel> + '_;
+
+    /// The bids, from the highest price to the lowest.
+    fn bid_levels(&self) -> impl Iterator<Item = PriceLevel> + '_;
+
+    fn best_ask(&self) -> Option<PriceLevel> {
+        self.ask_levels().next()
+    }
+
+    fn best_bid(&self) -> Option<PriceLevel> {
+        self.bid_levels().next()
+    }
+
+    /// The difference between the best ask and the best bid.
+    fn spread(&self) -> Option<Number> {
+        Some(self.best_ask()?.price - self.best_bid()?.price)
+    }
+
+    /// The spread in basis points of the mid price.
+    fn spread_bps(&self) -> Option<Number> {
+        bps(self.spread()?, self.mid()?)
+    }
+
+    /// The price halfway between the best ask and the best bid.
+    fn mid(&self) -> Option<Number> {
+        Some((self.best_ask()?.price + self.best_bid()?.price) / Number::from(2))
+    }
+
+    /// The mid price weighted by the quantities at the top of the book,
+    /// which moves towards the ask when bids outweigh asks.
+    fn microprice(&self) -> Option<Number> {
+        let (ask, bid) = (self.best_ask()?, self.best_bid()?);
+        let quantity = ask.quantity + bid.quantity;
+        if quantity == Number::default() {
+            return None;
+        }
+        Some((ask.price * bid.quantity + bid.price * ask.quantity) / quantity)
+    }
+
+    /// The quantity of the first `levels` levels of the side of the book,
+    /// where `Sell` are the asks and `Buy` the bids.
+    fn depth(&self, side: Side, levels: usize) -> Number {
+        side_levels(self, side)
+            .take(levels)
+            .map(|level| level.quantity)
+            .sum()
+    }
+
+    /// The quantity of the levels of the side of the book whose price is
+    /// within `percent` percent of the best price of that side.
+    fn depth_within(&self, side: Side, percent: Number) -> Number {
+        let best = match side_levels(self, side).next() {
+            | Some(level) => level.price,
+            | None => return Number::default(),
+        };
+        let distance = best * percent / Number::from(100);
+        side_levels(self, side)
+            .take_while(|level| match side {
+                | Side::Sell => level.price <= best + distance,
+                | Side::Buy => level.price >= best - distance,
+            })
+            .map(|level| level.quantity)
+            .sum()
+    }
+
+    /// The average price of an order of the side for `quantity`, filled
+    /// against the book. A buy order is filled with the asks, and a sell
+    /// order with the bids. Returns `None` if the book is not deep enough.
+    fn vwap(&self, side: Side, quantity: Number) -> Option<Number> {
+        if quantity <= Number::default() {
+            return None;
+        }
+        let mut remaining = quantity;
+        let mut cost = Number::default();
+        let levels = match side {
+            | Side::Buy => side_levels(self, Side::Sell),
+            | Side::Sell => side_levels(self, Side::Buy),
+        };
+        for level in levels {
+            let filled = if level.quantity < remaining {
+                level.quantity
+            } else {
+                remaining
+            };
+            cost += filled * level.price;
+            remaining -= filled;
+            if remaining == Number::default() {
+                return Some(cost / quantity);
+            }
+        }
+        None
+    }
+
+    /// How much worse than the best price the average price of an order
+    /// of the side for `quantity` is, in basis points.
+    fn slippage_bps(&self, side: Side, quantity: Number) -> Option<Number> {
+        let vwap = self.vwap(side, quantity)?;
+        match side {
+            | Side::Buy => {
+                let best = self.best_ask()?.price;
+                bps(vwap - best, best)
+            }
+            | Side::Sell => {
+                let best = self.best_bid()?.price;
+                bps(best - vwap, best)
+            }
+        }
+    }
+
+    /// The difference between the bid and ask quantities of the first
+    /// `levels` levels, over their sum. It ranges from -1, when there are
+    /// only asks, to 1, when there are only bids.
+    fn imbalance(&self, levels: usize) -> Option<Number> {
+        let bids = self.depth(Side::Buy, levels);
+        let asks = self.depth(Side::Sell, levels);
+        let total = bids + asks;
+        if total == Number::default() {
+            return None;
+        }
+        Some((bids - asks) / total)
+    }
+}
+
+// the asks for `Sell`, and the bids for `Buy`.
+fn side_levels<B: BookAnalytics + ?Sized>(
+    book: &B,
+    side: Side,
+) -> Box<dyn Iterator<Item = PriceLevel> + '_> {
+    match side {
+        | Side::Sell => Box::new(book.ask_levels()),
+        | Side::Buy => Box::new(book.bid_levels()),
+    }
+}
+
+fn bps(value: Number, of: Number) -> Option<Number> {
+    if of == Number::default() {
+        return None;
+    }
+    Some(value * Number::from(10_000) / of)
+}
+
+impl BookAnalytics for LocalOrderBook {
+    fn ask_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks()
+    }
+
+    fn bid_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids()
+    }
+}
+
+// Levels without quantity mark deletions, and are not part of the book.
+impl BookAnalytics for Orderbook {
+    fn ask_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks()
+            .iter()
+            .copied()
+            .filter(|level| !level.is_deletion())
+    }
+
+    fn bid_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids()
+            .iter()
+            .copied()
+            .filter(|level| !level.is_deletion())
+    }
+}
+
+impl BookAnalytics for TopOrder {
+    fn ask_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        std::iter::once(PriceLevel {
+            price: self.ask_price,
+            quantity: self.ask_quantity,
+        })
+    }
+
+    fn bid_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        std::iter::once(PriceLevel {
+            price: self.bid_price,
+            quantity: self.bid_quantity,
+        })
+    }
+}
+
+#[test]
+fn analytics() {
+    let book = super::local::book(
+        1,
+        &[("102", "1"), ("104", "2"), ("110", "4")],
+        &[("98", "3"), ("97", "1")],
+    );
+    let n = |n: i32| Number::from(n);
+    assert_eq!(book.spread(), Some(n(4)));
+    assert_eq!(book.mid(), Some(n(100)));
+    assert_eq!(book.spread_bps(), Some(n(400)));
+    // (102 * 3 + 98 * 1) / 4
+    assert_eq!(book.microprice(), Some(n(101)));
+    assert_eq!(book.depth(Side::Sell, 2), n(3));
+    assert_eq!(book.depth_within(Side::Sell, n(5)), n(3));
+    assert_eq!(
+        book.vwap(Side::Buy, n(3)),
+        Some((n(102) + n(104) * n(2)) / n(3))
+    );
+    assert_eq!(
+        book.vwap(Side::Sell, n(4)),
+        Some((n(98) * n(3) + n(97)) / n(4))
+    );
+    assert_eq!(book.vwap(Side::Sell, n(5)), None);
+    assert_eq!(book.slippage_bps(Side::Sell, n(3)), Some(n(0)));
+    assert_eq!(book.imbalance(1), Some(n(2) / n(4)));
+
+    let local = LocalOrderBook::from_snapshot("ETHBTC", &book);
+    assert_eq!(local.microprice(), book.microprice());
+}
+
+#[test]
+fn analytics_of_empty_books() {
+    let book = super::local::book(1, &[], &[]);
+    assert_eq!(book.best_ask(), None);
+    assert_eq!(book.spread(), None);
+    assert_eq!(book.spread_bps(), None);
+    assert_eq!(book.mid(), None);
+    assert_eq!(book.microprice(), None);
+    assert_eq!(book.depth(Side::Buy, 5), Number::default());
+    assert_eq!(
+        book.depth_within(Side::Sell, Number::from(1)),
+        Number::default()
+    );
+    assert_eq!(book.vwap(Side::Buy, Number::from(1)), None);
+    assert_eq!(book.slippage_bps(Side::Sell, Number::from(1)), None);
+    assert_eq!(book.imbalance(5), None);
+}
+
+#[test]
+fn analytics_of_notifications() {
+    let n = |n: i32| Number::from(n);
+    let top = r#"{"ch":"orderbook/top/1000ms","data":{"ETHBTC":{"t":1614815872000,"a":"102","A":"1","b":"98","B":"3"}}}"#;
+    let top = &Response::decode(top).unwrap().as_top_order().unwrap()["ETHBTC"];
+    assert_eq!(top.mid(), n(100));
+    assert_eq!(BookAnalytics::mid(top), Some(top.mid()));
+    assert_eq!(BookAnalytics::spread(top), Some(top.spread()));
+    assert_eq!(top.microprice(), Some(n(101)));
+    assert_eq!(top.depth(Side::Buy, 5), n(3));
+    assert_eq!(top.vwap(Side::Buy, n(2)), None);
+
+    let partial = r#"{"ch":"orderbook/D5/100ms","data":{"ETHBTC":{"t":1614815872000,"s":1,"a":[["102","1"],["104","2"]],"b":[["98","3"],["97","1"]]}}}"#;
+    let partial = &Response::decode(partial).unwrap().as_orderbook().unwrap()["ETHBTC"];
+    assert_eq!(partial.spread(), Some(n(4)));
+    assert_eq!(partial.depth(Side::Sell, 5), n(3));
+    assert_eq!(partial.imbalance(2), Some(n(1) / n(7)));
+    assert_eq!(
+        partial.vwap(Side::Sell, n(4)),
+        Some((n(98) * n(3) + n(97)) / n(4))
+    );
+
+    // a level without quantity was removed, and is skipped.
+    let deleted = r#"{"ch":"orderbook/full","snapshot":{"ETHBTC":{"t":1614815872000,"s":1,"a":[["102","0"],["104","2"]],"b":[["98","3"]]}}}"#;
+    let deleted = Response::decode(deleted)
+        .unwrap()
+        .as_orderbook_event()
+        .unwrap();
+    let deleted = &deleted.data()["ETHBTC"];
+    assert_eq!(deleted.best_ask().unwrap().price, n(104));
+    assert_eq!(deleted.spread(), Some(n(6)));
+    assert_eq!(deleted.mid(), Some(n(101)));
+    assert_eq!(deleted.depth(Side::Sell, 5), n(2));
+}
@@ -0,0 +1,194 @@
+use crate::api::{Orderbook, PriceLevel, Side};
+use crate::prelude::*;
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+// prices are ordered with `partial_cmp`, since `f64` is not `Ord`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct Price(pub(crate) Number);
+
+impl Eq for Price {}
+
+impl PartialOrd for Price {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Price {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.partial_cmp(&other.0).unwrap_or(Ordering::Equal)
+    }
+}
+
+/// A change to a level of a book. A quantity of zero means
+/// the level was removed.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LevelChange {
+    /// `Sell` for asks and `Buy` for bids.
+    pub side: Side,
+    pub price: Number,
+    pub quantity: Number,
+    /// The quantity of the level before the change,
+    /// zero if the level was added.
+    pub previous: Number,
+}
+
+/// An update that does not follow the last one applied to the book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SequenceGap {
+    pub expected: i64,
+    pub received: i64,
+}
+
+/// The orderbook of a symbol, built from a snapshot and the updates
+/// that follow it.
+#[derive(Debug, Clone)]
+pub struct LocalOrderBook {
+    symbol: String,
+    sequence: i64,
+    timestamp: DateTime<Utc>,
+    asks: BTreeMap<Price, Number>,
+    bids: BTreeMap<Price, Number>,
+}
+
+impl LocalOrderBook {
+    pub fn from_snapshot(symbol: &str, snapshot: &Orderbook) -> Self {
+        let levels = |levels: &[PriceLevel]| {
+            levels
+                .iter()
+                .filter(|level| !level.is_deletion())
+                .map(|level| (Price(level.price), level.quantity))
+                .collect()
+        };
+        LocalOrderBook {
+            symbol: symbol.into(),
+            sequence: snapshot.sequence,
+            timestamp: snapshot.timestamp,
+            asks: levels(snapshot.asks()),
+            bids: levels(snapshot.bids()),
+        }
+    }
+
+    /// Applies an update, returning the levels that changed. Updates older
+    /// than the book are ignored, and updates that skip a sequence number
+    /// fail without modifying the book.
+    pub fn apply_update(&mut self, update: &Orderbook) -> Result<Vec<LevelChange>, SequenceGap> {
+        if update.sequence <= self.sequence {
+            return Ok(vec![]);
+        }
+        if update.sequence != self.sequence + 1 {
+            return Err(SequenceGap {
+                expected: self.sequence + 1,
+                received: update.sequence,
+            });
+        }
+        self.sequence = update.sequence;
+        self.timestamp = update.timestamp;
+        let asks = update.asks().iter().map(|level| (Side::Sell, level));
+        let bids = update.bids().iter().map(|level| (Side::Buy, level));
+        let changes = asks
+            .chain(bids)
+            .map(|(side, level)| {
+                let levels = match side {
+                    | Side::Sell => &mut self.asks,
+                    | Side::Buy => &mut self.bids,
+                };
+                let previous = if level.is_deletion() {
+                    levels.remove(&Price(level.price))
+                } else {
+                    levels.insert(Price(level.price), level.quantity)
+                };
+                LevelChange {
+                    side,
+                    price: level.price,
+                    quantity: level.quantity,
+                    previous: previous.unwrap_or_default(),
+                }
+            })
+            .collect();
+        Ok(changes)
+    }
+
+    pub fn symbol(&self) -> &str {
+        &self.symbol
+    }
+
+    /// The sequence number of the last update applied.
+    pub fn sequence(&self) -> i64 {
+        self.sequence
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    /// The asks, from the lowest price to the highest.
+    pub fn asks(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks.iter().map(level)
+    }
+
+    /// The bids, from the highest price to the lowest.
+    pub fn bids(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids.iter().rev().map(level)
+    }
+
+    pub fn best_ask(&self) -> Option<PriceLevel> {
+        self.asks().next()
+    }
+
+    pub fn best_bid(&self) -> Option<PriceLevel> {
+        self.bids().next()
+    }
+}
+
+fn level((price, quantity): (&Price, &Number)) -> PriceLevel {
+    PriceLevel {
+        price: price.0,
+        quantity: *quantity,
+    }
+}
+
+#[cfg(test)]
+pub(crate) fn book(sequence: i64, asks: &[(&str, &str)], bids: &[(&str, &str)]) -> Orderbook {
+    serde_json::from_value(serde_json::json!({
+        "t": 1626866578796_i64,
+        "s": sequence,
+        "a": asks,
+        "b": bids,
+    }))
+    .unwrap()
+}
+
+#[test]
+fn apply_updates() {
+    let snapshot = book(
+        10,
+        &[("0.0606", "1"), ("0.0607", "2")],
+        &[("0.0604", "3"), ("0.0603", "4")],
+    );
+    let mut local = LocalOrderBook::from_snapshot("ETHBTC", &snapshot);
+    assert_eq!(local.best_ask().unwrap().quantity, Number::from(1));
+
+    let update = book(11, &[("0.0606", "0"), ("0.0608", "5")], &[("0.0605", "1")]);
+    let changes = local.apply_update(&update).unwrap();
+    assert_eq!(changes.len(), 3);
+    assert_eq!(changes[0].previous, Number::from(1));
+    assert_eq!(changes[1].previous, Number::from(0));
+    let asks: Vec<_> = local.asks().map(|level| level.quantity).collect();
+    assert_eq!(asks, [Number::from(2), Number::from(5)]);
+    let bids: Vec<_> = local.bids().map(|level| level.quantity).collect();
+    assert_eq!(bids, [Number::from(1), Number::from(3), Number::from(4)]);
+
+    // stale updates are ignored, and gaps are reported.
+    assert_eq!(local.apply_update(&update), Ok(vec![]));
+    let gap = local.apply_update(&book(13, &[], &[])).unwrap_err();
+    assert_eq!(
+        gap,
+        SequenceGap {
+            expected: 12,
+            received: 13
+        }
+    );
+    assert_eq!(local.sequence(), 11);
+}
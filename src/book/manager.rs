@@ -0,0 +1,340 @@
+use super::*;
+use crate::api::{Channel, Event, OrderbookMap};
+use crate::prelude::*;
+use crate::PublicClient;
+use std::collections::HashSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, RwLock};
+
+/// A notification of a change to the books of a `BookManager`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BookEvent {
+    /// The book of the symbol was replaced by a snapshot.
+    Snapshot { symbol: String },
+    /// Levels of the book of the symbol changed.
+    Update {
+        symbol: String,
+        /// The sequence number of the update.
+        sequence: i64,
+        changes: Vec<LevelChange>,
+    },
+    /// An update of the symbol was missed. Its book is unavailable until
+    /// the snapshot requested by the manager arrives.
+    Gap { symbol: String, gap: SequenceGap },
+    /// The websocket reconnected, so updates of the symbol may have been
+    /// missed. Its book is unavailable until the snapshot requested by the
+    /// manager arrives.
+    Reconnected { symbol: String },
+    /// A new snapshot of the symbol could not be requested. Its book is
+    /// unavailable until the manager is created again.
+    ResubscribeFailed { symbol: String, error: String },
+    /// The receiver did not keep up with the events, and `dropped` events
+    /// were discarded. Anything built from the events should be built
+    /// again from the books.
+    Lagged { dropped: usize },
+}
+
+type Books = Arc<RwLock<HashMap<String, LocalOrderBook>>>;
+
+// The number of events the receiver may fall behind by
+// before events are discarded.
+const CAPACITY: usize = 1024;
+// The number of attempts made to request a new snapshot.
+const ATTEMPTS: u32 = 3;
+
+/// Maintains the local orderbooks of a set of symbols from a subscription
+/// to the full orderbook. When an update is missed, or the websocket
+/// reconnects, the symbols are subscribed again to obtain fresh snapshots.
+///
+/// Events are never awaited by the manager, so a slow receiver cannot
+/// stall the websocket. If the receiver falls behind, events are discarded
+/// and reported with `BookEvent::Lagged`.
+#[derive(Clone)]
+pub struct BookManager {
+    books: Books,
+}
+
+// Sends events without blocking, counting the ones discarded.
+#[derive(Clone)]
+struct Events {
+    tx: Sender<BookEvent>,
+    dropped: Arc<AtomicUsize>,
+}
+
+// Requests new snapshots, at most one at a time per symbol.
+#[derive(Clone)]
+struct Resubscriptions {
+    client: PublicClient,
+    pending: Arc<Mutex<HashSet<String>>>,
+    events: Events,
+}
+
+impl BookManager {
+    /// Subscribes to the full orderbook of the symbols. The events
+    /// describing the changes to the books are sent to the receiver.
+    pub async fn new(
+        client: PublicClient,
+        symbols: &[impl AsRef<str>],
+    ) -> Result<(BookManager, Receiver<BookEvent>)> {
+        let reconnections = client.reconnections();
+        let (mut notifications, _) = client.subscribe_full_orderbook(symbols).await?;
+        let symbols: Vec<String> = symbols.iter().map(|s| s.as_ref().into()).collect();
+        let books = Books::default();
+        let (tx, rx) = mpsc::channel(CAPACITY);
+        let events = Events {
+            tx,
+            dropped: Arc::default(),
+        };
+        let resubscriptions = Resubscriptions {
+            client,
+            pending: Arc::default(),
+            events: events.clone(),
+        };
+        let manager = BookManager {
+            books: books.clone(),
+        };
+
+        spawn(restore_books(
+            reconnections,
+            books.clone(),
+            resubscriptions.clone(),
+            symbols,
+        ));
+        spawn(async move {
+            while let Some(notification) = notifications.recv().await {
+                let notified = apply(&mut *books.write().await, notification);
+                for event in notified {
+                    if let BookEvent::Gap { symbol, gap } = &event {
+                        warn!("orderbook of {} missed an update: {:?}", symbol, gap);
+                        resubscriptions.resubscribe(symbol);
+                    }
+                    if !events.send(event) {
+                        return;
+                    }
+                }
+            }
+        });
+        Ok((manager, rx))
+    }
+
+    /// A copy of the book of the symbol, if it is available.
+    pub async fn book(&self, symbol: &str) -> Option<LocalOrderBook> {
+        self.books.read().await.get(symbol).cloned()
+    }
+
+    /// Calls `f` with the book of the symbol, without copying it.
+    pub async fn with_book<T>(
+        &self,
+        symbol: &str,
+        f: impl FnOnce(&LocalOrderBook) -> T,
+    ) -> Option<T> {
+        self.books.read().await.get(symbol).map(f)
+    }
+
+    /// The symbols whose books are available.
+    pub async fn symbols(&self) -> Vec<String> {
+        self.books.read().await.keys().cloned().collect()
+    }
+}
+
+impl Events {
+    // Returns false if the receiver was dropped.
+    fn send(&self, event: BookEvent) -> bool {
+        let dropped = self.dropped.load(Ordering::Relaxed);
+        if dropped > 0 {
+            match self.tx.try_send(BookEvent::Lagged { dropped }) {
+                | Ok(()) => {
+                    self.dropped.fetch_sub(dropped, Ordering::Relaxed);
+                }
+                | Err(TrySendError::Full(_)) => {
+                    self.dropped.fetch_add(1, Ordering::Relaxed);
+                    return true;
+                }
+                | Err(TrySendError::Closed(_)) => return false,
+            }
+        }
+        match self.tx.try_send(event) {
+            | Ok(()) => true,
+            | Err(TrySendError::Full(_)) => {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+                true
+            }
+            | Err(TrySendError::Closed(_)) => false,
+        }
+    }
+}
+
+impl Resubscriptions {
+    // Requests a new snapshot of the symbol on the current subscription.
+    fn resubscribe(&self, symbol: &str) {
+        self.request(vec![symbol.into()], false);
+    }
+
+    // Subscribes the symbols again after a reconnection.
+    fn restore(&self, symbols: &[String]) {
+        self.request(symbols.to_vec(), true);
+    }
+
+    fn request(&self, symbols: Vec<String>, reconnected: bool) {
+        let symbols: Vec<String> = {
+            let mut pending = self.pending.lock().unwrap_or_else(|err| err.into_inner());
+            symbols
+                .into_iter()
+                .filter(|symbol| pending.insert(symbol.clone()))
+                .collect()
+        };
+        if symbols.is_empty() {
+            return;
+        }
+        let this = self.clone();
+        spawn(async move {
+            let mut result = Ok(());
+            for attempt in 0..ATTEMPTS {
+                if attempt > 0 {
+                    tokio::time::sleep(Duration::from_secs(1 << attempt)).await;
+                }
+                let channel = Channel::FullOrderbook;
+                result = if reconnected {
+                    this.client.restore(channel, &symbols).await
+                } else {
+                    this.client.resubscribe(channel, &symbols).await
+                }
+                .map(drop);
+                if result.is_ok() {
+                    break;
+                }
+            }
+            let mut pending = this.pending.lock().unwrap_or_else(|err| err.into_inner());
+            for symbol in symbols {
+                pending.remove(&symbol);
+                if let Err(err) = &result {
+                    error!("could not resubscribe to {}: {}", symbol, err);
+                    let error = err.to_string();
+                    this.events
+                        .send(BookEvent::ResubscribeFailed { symbol, error });
+                }
+            }
+        });
+    }
+}
+
+// Discards the books after every reconnection of the websocket, since
+// updates may have been missed, and subscribes the symbols again.
+async fn restore_books(
+    mut reconnections: tokio::sync::watch::Receiver<u64>,
+    books: Books,
+    resubscriptions: Resubscriptions,
+    symbols: Vec<String>,
+) {
+    while reconnections.changed().await.is_ok() {
+        warn!("orderbooks are stale after a reconnection");
+        let mut books = books.write().await;
+        for symbol in &symbols {
+            books.remove(symbol);
+            let symbol = symbol.clone();
+            if !resubscriptions
+                .events
+                .send(BookEvent::Reconnected { symbol })
+            {
+                return;
+            }
+        }
+        drop(books);
+        resubscriptions.restore(&symbols);
+    }
+}
+
+// Applies a notification of the full orderbook to the books. Books with a
+// gap are removed until their next snapshot.
+fn apply(
+    books: &mut HashMap<String, LocalOrderBook>,
+    notification: Event<OrderbookMap>,
+) -> Vec<BookEvent> {
+    let mut events = vec![];
+    match notification {
+        | Event::Snapshot(snapshots) => {
+            for (symbol, snapshot) in snapshots {
+                let book = LocalOrderBook::from_snapshot(&symbol, &snapshot);
+                books.insert(symbol.clone(), book);
+                events.push(BookEvent::Snapshot { symbol });
+            }
+        }
+        | Event::Update(updates) => {
+            for (symbol, update) in updates {
+                let book = match books.get_mut(&symbol) {
+                    | Some(book) => book,
+                    | None => continue,
+                };
+                match book.apply_update(&update) {
+                    | Ok(changes) if changes.is_empty() => (),
+                    | Ok(changes) => events.push(BookEvent::Update {
+                        symbol,
+                        sequence: update.sequence,
+                        changes,
+                    }),
+                    | Err(gap) => {
+                        books.remove(&symbol);
+                        events.push(BookEvent::Gap { symbol, gap });
+                    }
+                }
+            }
+        }
+    }
+    events
+}
+
+#[test]
+fn lagging_events() {
+    let (tx, mut rx) = mpsc::channel(1);
+    let events = Events {
+        tx,
+        dropped: Arc::default(),
+    };
+    let snapshot = |symbol: &str| BookEvent::Snapshot {
+        symbol: symbol.into(),
+    };
+    assert!(events.send(snapshot("ETHBTC")));
+    // the channel is full, so events are discarded instead of awaited.
+    assert!(events.send(snapshot("BTCUSDT")));
+    assert_eq!(rx.try_recv().unwrap(), snapshot("ETHBTC"));
+    assert!(events.send(snapshot("BTCCLP")));
+    assert_eq!(rx.try_recv().unwrap(), BookEvent::Lagged { dropped: 1 });
+    // the event sent while reporting the lag is discarded as well.
+    assert!(events.send(snapshot("BTCCLP")));
+    assert_eq!(rx.try_recv().unwrap(), BookEvent::Lagged { dropped: 1 });
+    drop(rx);
+    assert!(!events.send(snapshot("BTCCLP")));
+}
+
+#[test]
+fn manage_books() {
+    let mut books = HashMap::new();
+    let notification = |symbol: &str, book| HashMap::from([(symbol.to_string(), book)]);
+    let snapshot = local::book(1, &[("2", "1")], &[("1", "1")]);
+    let events = apply(
+        &mut books,
+        Event::Snapshot(notification("ETHBTC", snapshot)),
+    );
+    assert_eq!(
+        events,
+        [BookEvent::Snapshot {
+            symbol: "ETHBTC".into()
+        }]
+    );
+
+    let update = local::book(2, &[("2", "0")], &[]);
+    let events = apply(&mut books, Event::Update(notification("ETHBTC", update)));
+    assert!(matches!(&events[0], BookEvent::Update { changes, .. } if changes.len() == 1));
+    assert!(books["ETHBTC"].best_ask().is_none());
+
+    let update = local::book(4, &[], &[("1", "2")]);
+    let events = apply(&mut books, Event::Update(notification("ETHBTC", update)));
+    assert!(matches!(events[0], BookEvent::Gap { .. }));
+    assert!(books.is_empty());
+
+    // updates are ignored until the next snapshot.
+    let update = local::book(5, &[], &[("1", "2")]);
+    assert!(apply(&mut books, Event::Update(notification("ETHBTC", update))).is_empty());
+}
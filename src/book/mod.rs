@@ -0,0 +1,27 @@
+//! # Order books
+//! Local copies of the full orderbooks of the exchange, kept up to date
+//! from the `orderbook/full` channel, and calculations over orderbooks.
+//! ```no_run
+//! # async fn doc(client: cryptomarket::PublicClient) -> Result<(), cryptomarket::Error> {
+//! use cryptomarket::book::{BookEvent, BookManager};
+//!
+//! let (manager, mut events) = BookManager::new(client, &["ETHBTC"]).await?;
+//! while let Some(event) = events.recv().await {
+//!     if let BookEvent::Update { symbol, .. } = event {
+//!         let best_bid = manager.with_book(&symbol, |book| book.best_bid()).await;
+//!         println!("{}: {:?}", symbol, best_bid);
+//!     }
+//! }
+//! # Ok(()) }
+//! ```
+pub use aggregate::*;
+pub use analytics::*;
+pub use local::*;
+pub use manager::*;
+pub use synthetic::*;
+
+mod aggregate;
+mod analytics;
+mod local;
+mod manager;
+mod synthetic;
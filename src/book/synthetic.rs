@@ -0,0 +1,207 @@
+use super::BookAnalytics;
+use crate::api::{Fee, PriceLevel};
+use crate::prelude::*;
+
+/// An orderbook used as a step of a synthetic book.
+#[derive(Debug, Clone)]
+pub struct Leg {
+    asks: Vec<PriceLevel>,
+    bids: Vec<PriceLevel>,
+}
+
+/// An orderbook implied by chaining the books of several symbols, such
+/// as BTCCLP from BTCUSDT and USDTCLP. Its asks are the prices of buying
+/// the base currency of the first leg with the quote currency of the last
+/// one, going through every leg, and its bids the prices of selling it.
+/// ```
+/// # use cryptomarket::api::{Fee, Orderbook};
+/// # fn doc(btcusdt: Orderbook, usdtclp: Orderbook, fees: [Fee; 2]) -> Option<()> {
+/// use cryptomarket::book::{BookAnalytics, Leg, SyntheticBook};
+///
+/// let btcclp = SyntheticBook::new(vec![
+///     Leg::new(&btcusdt).with_fees(&fees[0])?,
+///     Leg::new(&usdtclp).with_fees(&fees[1])?,
+/// ]);
+/// println!("implied BTCCLP mid: {:?}", btcclp.mid());
+/// # Some(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct SyntheticBook {
+    asks: Vec<PriceLevel>,
+    bids: Vec<PriceLevel>,
+}
+
+impl Leg {
+    /// Takes the current levels of the book. Levels without a positive
+    /// price are left out, as they cannot be chained.
+    pub fn new(book: &impl BookAnalytics) -> Self {
+        let zero = Number::default();
+        let priced = |level: &PriceLevel| level.price > zero;
+        Leg {
+            asks: book.ask_levels().filter(priced).collect(),
+            bids: book.bid_levels().filter(priced).collect(),
+        }
+    }
+
+    /// Uses the book in the opposite direction, pricing its quote currency
+    /// in its base currency. For example, a USDTCLP book can be used to
+    /// go from USDT to CLP, and inverted to go from CLP to USDT.
+    pub fn inverted(self) -> Self {
+        let invert = |levels: Vec<PriceLevel>| {
+            levels
+                .into_iter()
+                .filter(|level| level.price != Number::default())
+                .map(|level| PriceLevel {
+                    price: Number::from(1) / level.price,
+                    quantity: level.quantity * level.price,
+                })
+                .collect()
+        };
+        Leg {
+            asks: invert(self.bids),
+            bids: invert(self.asks),
+        }
+    }
+
+    /// Applies the fee rate charged when crossing the book, such as the
+    /// `take_rate` of the `Fee` of the symbol. Buying becomes more
+    /// expensive, and selling returns less. Returns `None` unless the rate
+    /// is at least zero and less than one.
+    pub fn with_fee(mut self, rate: Number) -> Option<Self> {
+        let one = Number::from(1);
+        if !(rate >= Number::default() && rate < one) {
+            return None;
+        }
+        for ask in &mut self.asks {
+            ask.price /= one - rate;
+        }
+        for bid in &mut self.bids {
+            bid.price *= one - rate;
+        }
+        Some(self)
+    }
+
+    /// Applies the `take_rate` of the fees of the symbol, see `with_fee`.
+    pub fn with_fees(self, fee: &Fee) -> Option<Self> {
+        self.with_fee(fee.take_rate)
+    }
+}
+
+impl SyntheticBook {
+    /// Chains the legs. The quote currency of each leg must be the base
+    /// currency of the next one, inverting the legs as needed.
+    pub fn new(legs: Vec<Leg>) -> Self {
+        let mut legs = legs.into_iter();
+        let first = match legs.next() {
+            | Some(leg) => leg,
+            | None => return SyntheticBook::default(),
+        };
+        let Leg { asks, bids } = legs.fold(first, |chained, leg| Leg {
+            asks: chain(&chained.asks, &leg.asks),
+            bids: chain(&chained.bids, &leg.bids),
+        });
+        SyntheticBook { asks, bids }
+    }
+}
+
+// Chains the levels of X priced in Y with the levels of Y priced in Z,
+// returning the levels of X priced in Z. The quantity of each level is
+// the amount of X that both books can fill at its price.
+fn chain(first: &[PriceLevel], second: &[PriceLevel]) -> Vec<PriceLevel> {
+    let zero = Number::default();
+    let mut levels = vec![];
+    let (mut first, mut second) = (first.iter(), second.iter());
+    let (mut a, mut b) = match (first.next(), second.next()) {
+        | (Some(a), Some(b)) => (*a, *b),
+        | _ => return levels,
+    };
+    loop {
+        // the amount of X the remaining Y of the second level pays for.
+        let covered = b.quantity / a.price;
+        let quantity = if a.quantity < covered {
+            a.quantity
+        } else {
+            covered
+        };
+        if quantity > zero {
+            levels.push(PriceLevel {
+                price: a.price * b.price,
+                quantity,
+            });
+        }
+        a.quantity -= quantity;
+        b.quantity -= quantity * a.price;
+        if quantity == covered {
+            b = match second.next() {
+                | Some(b) => *b,
+                | None => break,
+            };
+        }
+        if a.quantity <= zero {
+            a = match first.next() {
+                | Some(a) => *a,
+                | None => break,
+            };
+        }
+    }
+    levels
+}
+
+impl BookAnalytics for SyntheticBook {
+    fn ask_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.asks.iter().copied()
+    }
+
+    fn bid_levels(&self) -> impl Iterator<Item = PriceLevel> + '_ {
+        self.bids.iter().copied()
+    }
+}
+
+#[test]
+fn cross_rates() {
+    let n = |n: &str| n.parse::<Number>().unwrap();
+    let btcusdt = super::local::book(1, &[("100", "1")], &[("99", "2")]);
+    let usdtclp = super::local::book(1, &[("10", "50"), ("11", "100")], &[("8", "1000")]);
+    let btcclp = SyntheticBook::new(vec![Leg::new(&btcusdt), Leg::new(&usdtclp)]);
+    let asks: Vec<_> = btcclp.ask_levels().map(|l| (l.price, l.quantity)).collect();
+    assert_eq!(asks, [(n("1000"), n("0.5")), (n("1100"), n("0.5"))]);
+    assert_eq!(btcclp.best_bid().unwrap().price, n("792"));
+
+    // going from CLP to BTC through the same books.
+    let clpbtc = SyntheticBook::new(vec![
+        Leg::new(&usdtclp).inverted(),
+        Leg::new(&btcusdt).inverted(),
+    ]);
+    assert_eq!(clpbtc.best_bid().unwrap().price, n("1") / n("1000"));
+
+    let fee = Fee {
+        symbol: "BTCUSDT".into(),
+        take_rate: n("0.5"),
+        make_rate: n("0.1"),
+    };
+    let with_fees = SyntheticBook::new(vec![
+        Leg::new(&btcusdt).with_fees(&fee).unwrap(),
+        Leg::new(&usdtclp),
+    ]);
+    assert_eq!(with_fees.best_ask().unwrap().price, n("2000"));
+    assert_eq!(with_fees.best_bid().unwrap().price, n("396"));
+}
+
+#[test]
+fn invalid_legs() {
+    let n = |n: &str| n.parse::<Number>().unwrap();
+    let btcusdt = super::local::book(1, &[("100", "1")], &[("99", "2")]);
+    assert!(Leg::new(&btcusdt).with_fee(n("1")).is_none());
+    assert!(Leg::new(&btcusdt).with_fee(n("1.5")).is_none());
+    assert!(Leg::new(&btcusdt).with_fee(n("-0.1")).is_none());
+    assert!(Leg::new(&btcusdt).with_fee(n("0")).is_some());
+
+    // a zero priced level is left out instead of being divided by.
+    let zero = super::local::book(1, &[("0", "5"), ("100", "1")], &[("0", "5")]);
+    let usdtclp = super::local::book(1, &[("10", "500")], &[("8", "1000")]);
+    let book = SyntheticBook::new(vec![Leg::new(&zero), Leg::new(&usdtclp)]);
+    let asks: Vec<_> = book.ask_levels().map(|l| (l.price, l.quantity)).collect();
+    assert_eq!(asks, [(n("1000"), n("1"))]);
+    assert!(book.best_bid().is_none());
+}
@@ -1,5 +1,6 @@
 use crate::api;
 use crate::prelude::*;
+use crate::triple_client::Endpoint;
 use crate::PublicClient;
 use crate::TradingClient;
 use api::*;
@@ -18,66 +19,157 @@ pub struct ClientPool {
 
 impl ClientPool {
     pub async fn new(private_key: &str, public_key: &str) -> Result<Self> {
-        let pool = Pool::new(private_key, public_key).await?;
+        Self::with_credentials(Credentials::new(private_key, public_key)).await
+    }
+
+    pub async fn with_credentials(credentials: Credentials) -> Result<Self> {
+        Self::with_auth(credentials.into()).await
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub async fn with_signer(api_key: &str, signer: impl Signer + 'static) -> Result<Self> {
+        Self::with_auth(Auth::new(api_key, signer)).await
+    }
+
+    async fn with_auth(auth: Auth) -> Result<Self> {
+        let pool = Pool::new(auth.clone()).await?;
         Ok(Self {
             pool,
-            wallet: WalletClient::new(private_key, public_key).await?,
-            trading: TradingClient::new(private_key, public_key).await?,
+            wallet: WalletClient::with_auth(auth.clone()).await?,
+            trading: TradingClient::with_auth(auth).await?,
         })
     }
 
-    pub async fn subscribe_trades(&self, symbols: &[&str]) -> Result<(Receiver<TradeMap>, Subs)> {
+    /// Sends a request for any method of the API, including the ones
+    /// not yet wrapped by this client. Returns the `result` field of the
+    /// response. The request is sent to the trading client for `spot_`
+    /// methods, to the wallet client for wallet balance and transaction
+    /// methods, and to a public client otherwise.
+    pub async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        match Endpoint::of(method) {
+            | Endpoint::Trading => self.trading.call(method, params).await,
+            | Endpoint::Wallet => self.wallet.call(method, params).await,
+            | Endpoint::Public => self.pool.read().await.client().call(method, params).await,
+        }
+    }
+
+    /// Subscribes to any channel of the API with the params given, using
+    /// a new connection. Returns the notifications as raw json, along with
+    /// the result of the subscription request.
+    pub async fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Receiver<serde_json::Value>, serde_json::Value)> {
+        let mut pool = self.pool.write().await;
+        let client = pool.new_client().await?;
+        client.subscribe_raw(channel, params).await
+    }
+
+    pub async fn subscribe_trades(
+        &self,
+        symbols: &[impl AsRef<str>],
+    ) -> Result<(Receiver<Event<TradeMap>>, Subs)> {
         let mut pool = self.pool.write().await;
         let client = pool.new_client().await?;
         let output = client.subscribe_trades(symbols).await?;
-        pool.subscribe_last(symbols, "trades");
+        pool.subscribe_last(symbols, Channel::Trades);
         Ok(output)
     }
 
     pub async fn subscribe_full_orderbook(
         &self,
-        symbols: &[&str],
-    ) -> Result<(Receiver<HashMap<String, Orderbook>>, Subs)> {
+        symbols: &[impl AsRef<str>],
+    ) -> Result<(Receiver<Event<OrderbookMap>>, Subs)> {
         let mut pool = self.pool.write().await;
         let client = pool.new_client().await?;
         let output = client.subscribe_full_orderbook(symbols).await?;
-        pool.subscribe_last(symbols, "orderbook");
+        pool.subscribe_last(symbols, Channel::FullOrderbook);
         Ok(output)
     }
     pub async fn subscribe_ticker(
         &self,
-        symbols: &[&str],
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
     ) -> Result<(Receiver<Ticker>, Subs)> {
         let mut pool = self.pool.write().await;
         let client = pool.new_client().await?;
         let output = client.subscribe_ticker(symbols, speed).await?;
-        pool.subscribe_last(symbols, "ticker");
+        pool.subscribe_last(symbols, Channel::Ticker(speed));
         Ok(output)
     }
     pub async fn subscribe_top_orderbook(
         &self,
-        symbols: &[&str],
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        speed: OrderbookSpeed,
     ) -> Result<(Receiver<TopOrderMap>, Subs)> {
         let mut pool = self.pool.write().await;
         let client = pool.new_client().await?;
         let output = client.subscribe_top_order(symbols, speed).await?;
-        pool.subscribe_last(symbols, "top orderbook");
+        pool.subscribe_last(symbols, Channel::TopOrderbook(speed));
         Ok(output)
     }
     pub async fn subscribe_partial_orderbook(
         &self,
-        symbols: &[&str],
-        depth: &str,
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        depth: OrderbookDepth,
+        speed: OrderbookSpeed,
     ) -> Result<(Receiver<HashMap<String, Orderbook>>, Subs)> {
         let mut pool = self.pool.write().await;
         let client = pool.new_client().await?;
         let output = client
             .subscribe_partial_orderbook(symbols, depth, speed)
             .await?;
-        pool.subscribe_last(symbols, "partial orderbook");
+        pool.subscribe_last(symbols, Channel::PartialOrderbook(depth, speed));
+        Ok(output)
+    }
+
+    pub async fn subscribe_price_ticker(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<PriceTickerMap>, Subs)> {
+        let mut pool = self.pool.write().await;
+        let client = pool.new_client().await?;
+        let output = client.subscribe_price_ticker(symbols, speed).await?;
+        pool.subscribe_last(symbols, Channel::PriceTicker(speed));
+        Ok(output)
+    }
+
+    pub async fn subscribe_ticker_batch(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<Ticker>, Subs)> {
+        let mut pool = self.pool.write().await;
+        let client = pool.new_client().await?;
+        let output = client.subscribe_ticker_batch(symbols, speed).await?;
+        pool.subscribe_last(symbols, Channel::TickerBatch(speed));
+        Ok(output)
+    }
+
+    pub async fn subscribe_price_ticker_batch(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<PriceTickerMap>, Subs)> {
+        let mut pool = self.pool.write().await;
+        let client = pool.new_client().await?;
+        let output = client.subscribe_price_ticker_batch(symbols, speed).await?;
+        pool.subscribe_last(symbols, Channel::PriceTickerBatch(speed));
+        Ok(output)
+    }
+
+    pub async fn subscribe_candles(
+        &self,
+        symbols: &[impl AsRef<str>],
+        period: CandlePeriod,
+        limit: i32,
+    ) -> Result<(Receiver<Event<CandleMap>>, Subs)> {
+        let mut pool = self.pool.write().await;
+        let client = pool.new_client().await?;
+        let output = client.subscribe_candles(symbols, period, limit).await?;
+        pool.subscribe_last(symbols, Channel::Candles(period));
         Ok(output)
     }
 
@@ -118,7 +210,7 @@ impl ClientPool {
     /// # let client = TripleClient::new("", "").await?;
     /// let fee = client.get_fee("BTCCLP").await?;
     /// ```
-    pub async fn get_fee(&self, symbol: &str) -> Result<Fee> {
+    pub async fn get_fee(&self, symbol: impl AsRef<str>) -> Result<Fee> {
         self.trading.get_fee(symbol).await
     }
 
@@ -126,7 +218,7 @@ impl ClientPool {
     /// ```no_run
     /// let balance = client.currency_balance("BTC").await?;
     /// ```
-    pub async fn currency_balance(&self, currency: &str) -> Result<Amount> {
+    pub async fn currency_balance(&self, currency: impl AsRef<str>) -> Result<Amount> {
         self.wallet.currency_balance(currency).await
     }
     /// Used to get the current wallet balance for all currencies.
@@ -154,7 +246,12 @@ impl ClientPool {
         self.wallet.subscribe_transactions().await
     }
 
-    pub async fn replace_order(&self, order_id: &str, quantity: f64, price: f64) -> Result<Order> {
+    pub async fn replace_order(
+        &self,
+        order_id: &str,
+        quantity: Number,
+        price: Number,
+    ) -> Result<Order> {
         self.trading.replace_order(order_id, quantity, price).await
     }
 
@@ -1,35 +1,39 @@
 use super::*;
 
 pub struct Pool {
-    private_key: String,
-    public_key: String,
+    auth: Auth,
     clients: Vec<PublicClient>,
-    subs: HashMap<(String, &'static str), usize>,
+    subs: HashMap<(String, Channel), usize>,
 }
 
 impl Pool {
-    pub async fn new(private_key: &str, public_key: &str) -> Result<Arc<RwLock<Self>>> {
+    pub async fn new(auth: Auth) -> Result<Arc<RwLock<Self>>> {
         Ok(Arc::new(RwLock::new(Self {
-            public_key: public_key.into(),
-            private_key: private_key.into(),
+            clients: vec![PublicClient::with_auth(auth.clone()).await?],
+            auth,
             subs: Default::default(),
-            clients: vec![PublicClient::new(private_key, public_key).await?],
         })))
     }
 
+    /// The client used for requests other than subscriptions.
+    pub fn client(&self) -> &PublicClient {
+        &self.clients[0]
+    }
+
     fn len(&self) -> usize {
         self.clients.len()
     }
 
     pub async fn new_client(&mut self) -> Result<&PublicClient> {
-        let new_client = PublicClient::new(&self.private_key, &self.public_key).await?;
+        let new_client = PublicClient::with_auth(self.auth.clone()).await?;
         self.clients.push(new_client);
         Ok(&self.clients[self.len() - 1])
     }
 
-    pub fn subscribe_last(&mut self, symbols: &[&str], channel: &'static str) {
+    pub fn subscribe_last(&mut self, symbols: &[impl AsRef<str>], channel: Channel) {
         for sym in symbols {
-            self.subs.insert(((*sym).into(), channel), self.len() - 1);
+            self.subs
+                .insert((sym.as_ref().into(), channel), self.len() - 1);
         }
     }
 }
@@ -0,0 +1,152 @@
+//! # Credentials
+//! The API keys used to authenticate the clients. The private key is
+//! zeroized when the credentials are dropped, and it is never printed
+//! by the `Debug` implementation.
+//!
+//! ```no_run
+//! # async fn doc() -> Result<(), cryptomarket::Error> {
+//! use cryptomarket::{Credentials, TradingClient};
+//!
+//! let credentials = Credentials::from_env()?;
+//! let client = TradingClient::with_credentials(credentials).await?;
+//! # Ok(()) }
+//! ```
+use crate::{api::Account, prelude::*};
+use std::{fmt, path::Path};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+const PRIVATE_KEY: &str = "private_key";
+const PUBLIC_KEY: &str = "public_key";
+
+/// A pair of API keys.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct Credentials {
+    private_key: String,
+    public_key: String,
+    #[zeroize(skip)]
+    account: Account,
+}
+
+/// A source of credentials, used to plug in custom secret stores.
+/// It is implemented for closures returning credentials.
+/// ```no_run
+/// # fn doc() -> Result<(), cryptomarket::Error> {
+/// use cryptomarket::Credentials;
+///
+/// let provider = || Ok(Credentials::new("private_key", "public_key"));
+/// let credentials = Credentials::from_provider(&provider)?;
+/// # Ok(()) }
+/// ```
+pub trait CredentialProvider {
+    fn credentials(&self) -> Result<Credentials>;
+}
+
+impl<F: Fn() -> Result<Credentials>> CredentialProvider for F {
+    fn credentials(&self) -> Result<Credentials> {
+        self()
+    }
+}
+
+impl Credentials {
+    pub fn new(private_key: impl Into<String>, public_key: impl Into<String>) -> Self {
+        Self {
+            private_key: private_key.into(),
+            public_key: public_key.into(),
+            account: Account::Main,
+        }
+    }
+
+    /// Marks the keys as belonging to a sub-account, so the values
+    /// returned by the clients are tagged with it.
+    pub fn for_sub_account(mut self, sub_account_id: impl Into<String>) -> Self {
+        self.account = Account::Sub(sub_account_id.into());
+        self
+    }
+
+    pub fn account(&self) -> &Account {
+        &self.account
+    }
+
+    /// Reads the credentials from the `private_key` and `public_key`
+    /// environment variables.
+    pub fn from_env() -> Result<Self> {
+        Self::from_env_vars(PRIVATE_KEY, PUBLIC_KEY)
+    }
+
+    /// Reads the credentials from the environment variables given.
+    pub fn from_env_vars(private_var: &str, public_var: &str) -> Result<Self> {
+        let var = |name: &str| {
+            std::env::var(name).map_err(|_| {
+                Error::Credentials(format!("environment variable \"{}\" not found", name))
+            })
+        };
+        Ok(Self::new(var(private_var)?, var(public_var)?))
+    }
+
+    /// Reads the credentials from a file of `key=value` lines,
+    /// with the keys `private_key` and `public_key`, such as a `.env` file.
+    /// Empty lines and lines starting with `#` are ignored.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let mut content = std::fs::read_to_string(path)?;
+        let credentials = Self::parse(&content);
+        content.zeroize();
+        credentials
+    }
+
+    /// Obtains the credentials from a custom provider.
+    pub fn from_provider(provider: &impl CredentialProvider) -> Result<Self> {
+        provider.credentials()
+    }
+
+    pub fn public_key(&self) -> &str {
+        &self.public_key
+    }
+
+    pub(crate) fn private_key(&self) -> &str {
+        &self.private_key
+    }
+
+    fn parse(content: &str) -> Result<Self> {
+        let mut private_key = None;
+        let mut public_key = None;
+        let lines = content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'));
+        for line in lines {
+            if let Some((key, value)) = line.split_once('=') {
+                let value = value.trim().trim_matches(|c| c == '"' || c == '\'');
+                match key.trim() {
+                    | PRIVATE_KEY => private_key = Some(value),
+                    | PUBLIC_KEY => public_key = Some(value),
+                    | _ => (),
+                }
+            }
+        }
+        let missing = |name: &str| Error::Credentials(format!("missing \"{}\" entry", name));
+        Ok(Self::new(
+            private_key.ok_or_else(|| missing(PRIVATE_KEY))?,
+            public_key.ok_or_else(|| missing(PUBLIC_KEY))?,
+        ))
+    }
+}
+
+impl fmt::Debug for Credentials {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Credentials")
+            .field("private_key", &"[redacted]")
+            .field("public_key", &self.public_key)
+            .field("account", &self.account)
+            .finish()
+    }
+}
+
+#[test]
+fn parse_credentials() {
+    let file = "# api keys\nprivate_key = \"secret\"\n\npublic_key=public\nother=1\n";
+    let credentials = Credentials::parse(file).unwrap();
+    assert_eq!(credentials.private_key(), "secret");
+    assert_eq!(credentials.public_key(), "public");
+    assert!(!format!("{:?}", credentials).contains("secret"));
+    assert!(Credentials::parse("public_key=public").is_err());
+}
@@ -1,20 +1,86 @@
-use crate::api::Response;
+use std::fmt;
 use thiserror::Error;
 use tokio::sync::mpsc::error::SendError;
+
+/// Identifies the request an error originated from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RequestContext {
+    /// The method of the request.
+    pub method: String,
+    /// The id of the request.
+    pub id: i64,
+    /// The url of the websocket the request was sent to.
+    pub endpoint: String,
+    /// The `client_order_id` of the order, for order operations.
+    pub client_order_id: Option<String>,
+}
+
+impl fmt::Display for RequestContext {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "method: {}, id: {}", self.method, self.id)?;
+        if let Some(client_order_id) = &self.client_order_id {
+            write!(f, ", client_order_id: {}", client_order_id)?;
+        }
+        write!(f, ", endpoint: {}", self.endpoint)
+    }
+}
+/// The errors of the crate.
+///
+/// Errors of requests to the API are returned as `Error::WithContext`,
+/// which wraps the error that occurred along with the request it occurred
+/// in. Patterns such as `Err(Error::Timeout)` no longer match these errors,
+/// so match on [`Error::inner`] instead.
+/// ```no_run
+/// # async fn doc(client: cryptomarket::TradingClient) {
+/// use cryptomarket::Error;
+///
+/// match client.get_orders().await {
+///     | Ok(orders) => println!("{:?}", orders),
+///     | Err(err) => match err.inner() {
+///         | Error::Timeout => println!("timed out"),
+///         | Error::APIResponse(error) => println!("rejected: {}", error),
+///         | _ => println!("{}", err),
+///     },
+/// }
+/// # }
+/// ```
 #[derive(Debug, Error)]
 #[non_exhaustive]
 pub enum Error {
     #[error("ClosedConnectionError.")]
     Closed,
 
+    /// An error returned by a request, along with the request's context.
+    #[error("{source} ({context})")]
+    WithContext {
+        context: Box<RequestContext>,
+        source: Box<Error>,
+    },
+
     #[error("TimeoutError: timeout while waiting for a response.")]
     Timeout,
 
     #[error("RecvError: this error is a bug in the cryptmkt crate, it should not occur. Any report will be appreciated.")]
     Error(#[from] tokio::sync::oneshot::error::RecvError),
 
-    #[error("UnexpectedResponse: Unexpected response type.")]
-    UnexpectedResponse,
+    #[error(
+        "UnexpectedResponse: expected {expected} in response to {method}, received: {response}."
+    )]
+    UnexpectedResponse {
+        /// The name of the type the response was expected to decode to.
+        expected: &'static str,
+        /// The method of the request.
+        method: String,
+        /// The response, as received.
+        response: serde_json::Value,
+    },
+
+    #[error("DecodingError: could not decode {message}: {source}")]
+    Decoding {
+        /// The message, as received.
+        message: String,
+        source: serde_json::Error,
+    },
 
     #[error("APIResponse: {0}.")]
     APIResponse(#[from] crate::api::Error),
@@ -28,6 +94,12 @@ pub enum Error {
     #[error("The string \"{0}\" does not represent a supported currency")]
     ParsingCurrency(String),
 
+    #[error("The string \"{0}\" does not represent a supported symbol")]
+    ParsingSymbol(String),
+
+    #[error("IoError: {0}")]
+    Io(#[from] std::io::Error),
+
     #[error("JoinError: {0}")]
     JoinError(#[from] tokio::task::JoinError),
 
@@ -41,22 +113,137 @@ pub enum Error {
     #[error("RequestError: {0}")]
     Request(#[from] reqwest::Error),
 
-    #[error("DotenvError: {0}")]
-    DotEnv(#[from] dotenv::Error),
+    #[error("CredentialsError: {0}")]
+    Credentials(String),
 
     #[error("SendError: {0}")]
     Send(#[from] SendError<crate::api::Response>),
 }
 
 impl Error {
-    pub fn unexpected(_: Response) -> Self {
-        Error::UnexpectedResponse
+    /// Creates an error for a response to `method` that
+    /// could not be decoded as a `T`, `msg` being the response as received.
+    pub fn unexpected<T>(method: impl Into<String>, msg: &str) -> Self {
+        Error::UnexpectedResponse {
+            expected: std::any::type_name::<T>(),
+            method: method.into(),
+            response: serde_json::from_str(msg).unwrap_or_else(|_| msg.into()),
+        }
     }
 
-    pub fn api_respose(&self) -> Option<&crate::api::Error> {
+    pub(crate) fn decoding(msg: &str, source: serde_json::Error) -> Self {
+        Error::Decoding {
+            message: msg.into(),
+            source,
+        }
+    }
+
+    /// Attaches the context of the request the error originated from.
+    pub(crate) fn with_context(self, context: RequestContext) -> Self {
+        match self {
+            | Self::WithContext { .. } => self,
+            | error => Self::WithContext {
+                context: Box::new(context),
+                source: Box::new(error),
+            },
+        }
+    }
+
+    /// The context of the request the error originated from, if any.
+    pub fn context(&self) -> Option<&RequestContext> {
+        match self {
+            | Self::WithContext { context, .. } => Some(context),
+            | _ => None,
+        }
+    }
+
+    /// The error without its request context.
+    /// ```no_run
+    /// # async fn doc(client: cryptomarket::TradingClient) {
+    /// use cryptomarket::Error;
+    ///
+    /// if let Err(err) = client.get_orders().await {
+    ///     if let Error::Timeout = err.inner() {
+    ///         println!("{:?} timed out", err.context());
+    ///     }
+    /// }
+    /// # }
+    /// ```
+    pub fn inner(&self) -> &Error {
         match self {
+            | Self::WithContext { source, .. } => source.inner(),
+            | error => error,
+        }
+    }
+
+    pub fn api_respose(&self) -> Option<&crate::api::Error> {
+        match self.inner() {
             | Self::APIResponse(error) => Some(error),
             | _ => None,
         }
     }
+
+    /// The error code returned by the exchange, if any.
+    pub fn error_code(&self) -> Option<crate::api::ErrorCode> {
+        self.api_respose().map(crate::api::Error::error_code)
+    }
+
+    /// Whether the operation may succeed if it is attempted again later.
+    /// This is the case for connection errors, timeouts and exchange
+    /// errors such as rate limits.
+    pub fn is_retryable(&self) -> bool {
+        match self.inner() {
+            | Self::Closed | Self::Timeout | Self::TokioTungstenite(_) => true,
+            | Self::APIResponse(error) => error.is_retryable(),
+            | _ => false,
+        }
+    }
+
+    /// Whether the error is caused by missing, invalid or
+    /// insufficiently privileged credentials.
+    pub fn is_auth_failure(&self) -> bool {
+        match self.inner() {
+            | Self::APIResponse(error) => error.is_auth_failure(),
+            | Self::Credentials(_) => true,
+            | _ => false,
+        }
+    }
+}
+
+#[test]
+fn unexpected_response() {
+    let msg = r#"{"id": 1, "result": true, "extra": 1}"#;
+    let response: crate::api::Response = serde_json::from_str(msg).unwrap();
+    let error = match response.as_spot_orders() {
+        | Err(_) => Error::unexpected::<Vec<crate::Order>>("spot_get_orders", msg),
+        | Ok(_) => panic!("a boolean result was decoded as orders"),
+    };
+    match error {
+        | Error::UnexpectedResponse {
+            expected,
+            method,
+            response,
+        } => {
+            assert!(expected.contains("Order"));
+            assert_eq!(method, "spot_get_orders");
+            assert_eq!(response["result"], true);
+            assert_eq!(response["extra"], 1);
+        }
+        | error => panic!("unexpected error: {}", error),
+    }
+}
+
+#[test]
+fn request_context() {
+    let context = RequestContext {
+        method: "spot_cancel_order".into(),
+        id: 1,
+        endpoint: "wss://api.exchange.cryptomkt.com/api/3/ws/trading".into(),
+        client_order_id: Some("f4c1xzp9sw5mbjt".into()),
+    };
+    let error = Error::Timeout.with_context(context.clone());
+    assert!(matches!(error.inner(), Error::Timeout));
+    assert_eq!(error.context(), Some(&context));
+    assert!(error.is_retryable());
+    assert!(error.to_string().contains("f4c1xzp9sw5mbjt"));
 }
@@ -3,10 +3,10 @@
 //!
 //! ```
 //! # || -> cryptomkt::Result {async {
-//! use dotenv::*;
-//! use cryptomkt::{TradingClient, NewOrderBuilder, Buy};
+//! use cryptomkt::{Credentials, TradingClient, NewOrderBuilder, Buy};
 //!
-//! let mut client = TradingClient::new(var("private_key")?, var("public_key")?).await?;
+//! let credentials = Credentials::from_env()?;
+//! let mut client = TradingClient::with_credentials(credentials).await?;
 //! let order = NewOrderBuilder::new()
 //!     .symbol("BTCCLP")
 //!     .side(Buy)
@@ -19,19 +19,33 @@
 //! ```
 
 #![warn(unused_crate_dependencies)]
+#![allow(clippy::result_large_err)]
+
+// criterion is only used by the benchmarks.
+#[cfg(test)]
+use criterion as _;
 
 use crate::prelude::*;
 use api::*;
-pub use error::Error;
+pub use error::{Error, RequestContext};
+#[cfg(feature = "decimal")]
+pub use rust_decimal;
 pub mod api;
 
 mod base_client;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+pub mod book;
 mod client_pool;
+mod credentials;
 mod error;
 mod prelude;
 mod public_client;
 #[cfg(feature = "rest-client")]
 mod rest_client;
+mod signer;
+pub mod tape;
+mod time_sync;
 mod trading_client;
 mod triple_client;
 mod type_alias;
@@ -40,9 +54,12 @@ mod wallet_client;
 const TIMEMOUT: Duration = Duration::from_secs(5);
 pub use api::{Order, Side, Side::*};
 pub use client_pool::ClientPool;
+pub use credentials::{CredentialProvider, Credentials};
 pub use public_client::PublicClient;
 #[cfg(feature = "rest-client")]
 pub use rest_client::RestClient;
+pub use signer::{HmacSigner, Signer, SignerOutput};
+pub use time_sync::{time_sync, TimeSync};
 pub use trading_client::TradingClient;
 pub use triple_client::TripleClient;
 pub use wallet_client::WalletClient;
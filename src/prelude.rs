@@ -3,8 +3,6 @@ pub(crate) use std::{collections::HashMap, sync::Arc, time::Duration};
 // pub(crate) use chrono::serde::ts_seconds;
 
 pub(crate) use log::*;
-#[cfg(feature = "log")]
-use log::{info, trace, warn};
 pub(crate) use ring::hmac;
 pub(crate) use serde::{Deserialize, Serialize};
 
@@ -15,8 +13,13 @@ pub(crate) use tokio::{
 };
 pub(crate) use tokio_tungstenite::tungstenite::Message;
 
-pub(crate) use crate::{api::Request, base_client::BaseClient};
 pub use crate::{api::Response, error::Error, wallet_client::WalletClient};
+pub(crate) use crate::{
+    api::{Number, Request},
+    base_client::BaseClient,
+    credentials::Credentials,
+    signer::{Auth, Signer},
+};
 
 pub type Result<T = (), E = Error> = std::result::Result<T, E>;
 pub use chrono::{DateTime, Utc};
@@ -1,7 +1,11 @@
 use super::base_client::BaseClient;
-use crate::api::{Orderbook, Request, Subscriptions as Subs, Ticker, TopOrderMap, TradeMap};
+use crate::api::{
+    CandleMap, CandlePeriod, Channel, Event, Orderbook, OrderbookDepth, OrderbookMap,
+    OrderbookSpeed, PriceTickerMap, Request, Subscriptions as Subs, Ticker, TickerSpeed,
+    TopOrderMap, TradeMap,
+};
 use crate::prelude::*;
-use tokio::sync::mpsc::{channel, Receiver};
+use tokio::sync::mpsc::{self, Receiver};
 
 const WEBSOCKET_URL: &str = "wss://api.exchange.cryptomkt.com/api/3/ws/public";
 
@@ -13,7 +17,20 @@ pub struct PublicClient {
 
 impl PublicClient {
     pub async fn new(private_key: &str, public_key: &str) -> Result<PublicClient> {
-        let client = BaseClient::new(private_key, public_key, WEBSOCKET_URL).await?;
+        Self::with_credentials(Credentials::new(private_key, public_key)).await
+    }
+
+    pub async fn with_credentials(credentials: Credentials) -> Result<PublicClient> {
+        Self::with_auth(credentials.into()).await
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub async fn with_signer(api_key: &str, signer: impl Signer + 'static) -> Result<PublicClient> {
+        Self::with_auth(Auth::new(api_key, signer)).await
+    }
+
+    pub(crate) async fn with_auth(auth: Auth) -> Result<PublicClient> {
+        let client = BaseClient::new(auth, WEBSOCKET_URL).await?;
 
         Ok(Self { client })
     }
@@ -25,123 +42,322 @@ impl PublicClient {
         self.client.is_auth().await
     }
 
-    pub async fn subscriptions(&self, channel: &str) -> Result<Response> {
-        let request = Request::subscriptions(channel);
+    /// Sends a request for any method of the API, including the ones
+    /// not yet wrapped by this client. Returns the `result` field of the
+    /// response. Only `method` and `params` are sent, so methods taking a
+    /// `ch` field, such as `subscriptions`, are not supported. Channels
+    /// can be subscribed to with `subscribe_raw` instead.
+    /// ```no_run
+    /// # async fn doc(client: cryptomarket::PublicClient) -> Result<(), cryptomarket::Error> {
+    /// use cryptomarket::{HmacSigner, Signer};
+    ///
+    /// // logs in with params built by hand, like `authenticate` does.
+    /// let timestamp = chrono::Utc::now().timestamp_millis();
+    /// let payload = timestamp.to_string();
+    /// let signature = HmacSigner::new("private_key").sign(payload.as_bytes()).await?;
+    /// let signature: String = signature.iter().map(|byte| format!("{:02x}", byte)).collect();
+    /// let params = serde_json::json!({
+    ///     "type": "HS256",
+    ///     "api_key": "public_key",
+    ///     "timestamp": timestamp,
+    ///     "signature": signature,
+    /// });
+    /// let logged_in = client.call("login", params).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        self.client.call(method, params).await
+    }
+
+    /// Subscribes to any channel of the API with the params given.
+    /// Returns the notifications as raw json, along with the result of
+    /// the subscription request.
+    pub async fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Receiver<serde_json::Value>, serde_json::Value)> {
+        self.client.subscribe_raw(channel, params).await
+    }
+
+    /// Subscribes to a market data channel (`trades` or any of the
+    /// `orderbook` channels), calling `f` with every notification. The
+    /// symbols are borrowed from the message instead of being copied,
+    /// avoiding the allocations of the owned subscriptions. `f` runs on
+    /// the task reading the websocket, so it should return quickly.
+    /// ```no_run
+    /// # async fn doc(client: cryptomarket::PublicClient) -> Result<(), cryptomarket::Error> {
+    /// use cryptomarket::api::{Channel, MarketVariant, OrderbookDepth, OrderbookSpeed};
+    ///
+    /// let channel = Channel::PartialOrderbook(OrderbookDepth::D5, OrderbookSpeed::Ms100);
+    /// client
+    ///     .subscribe_borrowed(channel, &["ETHBTC"], |market| {
+    ///         if let MarketVariant::PartialOrderbook(books) = market.data {
+    ///             for (symbol, book) in books {
+    ///                 println!("{}: {:?}", symbol, book);
+    ///             }
+    ///         }
+    ///     })
+    ///     .await?;
+    /// # Ok(()) }
+    /// ```
+    #[cfg(feature = "zero-copy")]
+    pub async fn subscribe_borrowed<F>(
+        &self,
+        channel: Channel,
+        symbols: &[impl AsRef<str>],
+        f: F,
+    ) -> Result<Subs>
+    where
+        F: Fn(crate::api::MarketData<'_>) + Send + Sync + 'static,
+    {
+        let channel = channel.to_string();
+        let channel = channel.as_str();
+        let request = Request::subscribe(channel, symbols);
+        self.client.subscribe_borrowed(channel, f).await;
+        self.client
+            .request_as(&request, Response::as_subscriptions)
+            .await
+    }
+
+    /// Unsubscribes the symbols from the channel, returning
+    /// the symbols that remain subscribed.
+    pub async fn unsubscribe(&self, channel: Channel, symbols: &[impl AsRef<str>]) -> Result<Subs> {
+        let channel = channel.to_string();
+        let request = Request::unsubscribe(&channel, symbols);
+        self.client
+            .request_as(&request, Response::as_subscriptions)
+            .await
+    }
+
+    /// Notifies of the reconnections of the websocket. Subscriptions
+    /// are not restored by a reconnection, and must be made again.
+    pub fn reconnections(&self) -> tokio::sync::watch::Receiver<u64> {
+        self.client.reconnections()
+    }
+
+    // Subscribes the symbols again, so a new snapshot of their data is
+    // sent to the current subscription of the channel.
+    pub(crate) async fn resubscribe(
+        &self,
+        channel: Channel,
+        symbols: &[impl AsRef<str>],
+    ) -> Result<Subs> {
+        self.unsubscribe(channel, symbols).await?;
+        self.restore(channel, symbols).await
+    }
+
+    // Sends the subscription request of the symbols, without registering
+    // a new subscription. Used to restore the current subscription of the
+    // channel after a reconnection.
+    pub(crate) async fn restore(
+        &self,
+        channel: Channel,
+        symbols: &[impl AsRef<str>],
+    ) -> Result<Subs> {
+        let channel = channel.to_string();
+        let request = Request::subscribe(&channel, symbols);
+        self.client
+            .request_as(&request, Response::as_subscriptions)
+            .await
+    }
+
+    pub async fn subscriptions(&self, channel: Channel) -> Result<Response> {
+        let channel = channel.to_string();
+        let request = Request::subscriptions(&channel);
         self.client.request(&request).await
     }
-    /// registers a subscription to the trades channel.
+    /// registers a subscription to the trades channel. A snapshot of
+    /// the last trades of each symbol is sent first, followed by new trades.
     /// ```no_run
-    /// let (tx, res) = client.subscribe_trades().await?;
+    /// # async fn doc(client: cryptomarket::PublicClient) -> Result<(), cryptomarket::Error> {
+    /// let (mut rx, res) = client.subscribe_trades(&["ETHBTC"]).await?;
     /// println!("current subscriptions: {:?}", res);
-    /// while Some(trade) = tx.recv().await {
-    ///     println!("{:?}", trade);
+    /// while let Some(trades) = rx.recv().await {
+    ///     println!("snapshot: {}, {:?}", trades.is_snapshot(), trades.data());
     /// }
+    /// # Ok(()) }
     /// ```
-    pub async fn subscribe_trades(&self, sym: &[&str]) -> Result<(Receiver<TradeMap>, Subs)> {
-        let request = Request::subscribe("trades", sym);
-        let (tx, rx) = channel(2);
+    pub async fn subscribe_trades(
+        &self,
+        sym: &[impl AsRef<str>],
+    ) -> Result<(Receiver<Event<TradeMap>>, Subs)> {
+        let channel = Channel::Trades.to_string();
+        let request = Request::subscribe(&channel, sym);
+        let (tx, rx) = mpsc::channel(2);
+        self.client
+            .subscribe(&channel, tx.clone(), |res| res.as_trades_event())
+            .await;
         let res = self
             .client
-            .request(&request)
-            .await?
-            .as_subscriptions()
-            .map_err(Error::unexpected)?;
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
 
-        self.client
-            .subscribe("trades", tx.clone(), |res| res.as_trades())
-            .await;
         Ok((rx, res))
     }
 
+    /// Subscribes to the full orderbooks of the symbols. A snapshot of
+    /// each book is sent first, followed by the levels that changed.
+    /// See [`crate::book::BookManager`] to maintain local copies of the books.
     pub async fn subscribe_full_orderbook(
         &self,
-        symbols: &[&str],
-    ) -> Result<(Receiver<HashMap<String, Orderbook>>, Subs)> {
-        let request = Request::subscribe("orderbook/full", symbols);
+        symbols: &[impl AsRef<str>],
+    ) -> Result<(Receiver<Event<OrderbookMap>>, Subs)> {
+        let channel = Channel::FullOrderbook.to_string();
+        let request = Request::subscribe(&channel, symbols);
 
-        let (tx, rx) = channel(2);
+        let (tx, rx) = mpsc::channel(2);
         self.client
-            .subscribe("orderbook/full", tx.clone(), |res| res.as_orderbook())
+            .subscribe(&channel, tx.clone(), |res| res.as_orderbook_event())
             .await;
         let res = self
             .client
-            .request(&request)
-            .await?
-            .as_subscriptions()
-            .map_err(Error::unexpected)?;
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
 
         Ok((rx, res))
     }
 
-    /// The only valid speeds are "100ms", "500ms" and "1000ms".
-    /// The valid depths are top, D5, D10, D20
     pub async fn subscribe_partial_orderbook(
         &self,
-        symbols: &[&str],
-        depth: &str,
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        depth: OrderbookDepth,
+        speed: OrderbookSpeed,
     ) -> Result<(Receiver<HashMap<String, Orderbook>>, Subs)> {
-        let (tx, rx) = channel(2);
-        let channel = format!("orderbook/{}/{}", depth, speed);
+        let (tx, rx) = mpsc::channel(2);
+        let channel = Channel::PartialOrderbook(depth, speed).to_string();
         let request = Request::subscribe(&channel, symbols);
         self.client
             .subscribe(&channel, tx, |res| res.as_orderbook())
             .await;
         let res = self
             .client
-            .request(&request)
-            .await?
-            .as_subscriptions()
-            .map_err(Error::unexpected)?;
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
         Ok((rx, res))
     }
 
-    /// valid channels are 1s and 3s
     pub async fn subscribe_ticker(
         &self,
-        symbols: &[&str],
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<Ticker>, Subs)> {
+        let (tx, rx) = mpsc::channel(2);
+        let channel = Channel::Ticker(speed).to_string();
+        let request = Request::subscribe(&channel, symbols);
+        self.client
+            .subscribe(&channel, tx, |res| res.as_ticker())
+            .await;
+        let res = self
+            .client
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
+        Ok((rx, res))
+    }
+
+    /// Subscribes to the mini-tickers of the symbols, which are
+    /// cheaper than full tickers when tracking many symbols.
+    pub async fn subscribe_price_ticker(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<PriceTickerMap>, Subs)> {
+        let (tx, rx) = mpsc::channel(2);
+        let channel = Channel::PriceTicker(speed).to_string();
+        let request = Request::subscribe(&channel, symbols);
+        self.client
+            .subscribe(&channel, tx, |res| res.as_price_ticker())
+            .await;
+        let res = self
+            .client
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
+        Ok((rx, res))
+    }
+
+    /// Subscribes to the tickers of the symbols, delivered together in
+    /// one map per interval. Use `&["*"]` to subscribe to every symbol.
+    pub async fn subscribe_ticker_batch(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
     ) -> Result<(Receiver<Ticker>, Subs)> {
-        let (tx, rx) = channel(2);
-        let channel = format!("ticker/{}", speed);
+        let (tx, rx) = mpsc::channel(2);
+        let channel = Channel::TickerBatch(speed).to_string();
         let request = Request::subscribe(&channel, symbols);
         self.client
             .subscribe(&channel, tx, |res| res.as_ticker())
             .await;
         let res = self
             .client
-            .request(&request)
-            .await?
-            .as_subscriptions()
-            .map_err(Error::unexpected)?;
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
+        Ok((rx, res))
+    }
+
+    /// Subscribes to the mini-tickers of the symbols, delivered together
+    /// in one map per interval. Use `&["*"]` to subscribe to every symbol.
+    pub async fn subscribe_price_ticker_batch(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<PriceTickerMap>, Subs)> {
+        let (tx, rx) = mpsc::channel(2);
+        let channel = Channel::PriceTickerBatch(speed).to_string();
+        let request = Request::subscribe(&channel, symbols);
+        self.client
+            .subscribe(&channel, tx, |res| res.as_price_ticker())
+            .await;
+        let res = self
+            .client
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
+        Ok((rx, res))
+    }
+
+    /// Subscribes to the candles of the symbols. The last `limit` candles
+    /// of each symbol are sent as a snapshot, followed by updates to the
+    /// current candle.
+    pub async fn subscribe_candles(
+        &self,
+        symbols: &[impl AsRef<str>],
+        period: CandlePeriod,
+        limit: i32,
+    ) -> Result<(Receiver<Event<CandleMap>>, Subs)> {
+        let (tx, rx) = mpsc::channel(2);
+        let channel = Channel::Candles(period).to_string();
+        let request = Request::subscribe_limit(&channel, symbols, limit);
+        self.client
+            .subscribe(&channel, tx, |res| res.as_candles())
+            .await;
+        let res = self
+            .client
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
         Ok((rx, res))
     }
 
     pub async fn subscribe_top_order(
         &self,
-        symbols: &[&str],
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        speed: OrderbookSpeed,
     ) -> Result<(Receiver<TopOrderMap>, Subs)> {
-        let (tx, rx) = channel(2);
-        let channel = format!("orderbook/top/{}", speed);
+        let (tx, rx) = mpsc::channel(2);
+        let channel = Channel::TopOrderbook(speed).to_string();
         let request = Request::subscribe(&channel, symbols);
         self.client
             .subscribe(&channel, tx, |res| res.as_top_order())
             .await;
         let res = self
             .client
-            .request(&request)
-            .await?
-            .as_subscriptions()
-            .map_err(Error::unexpected)?;
+            .request_as(&request, Response::as_subscriptions)
+            .await?;
         Ok((rx, res))
     }
 }
 
 async fn _auth_client() -> Result<PublicClient> {
-    dotenv::dotenv()?;
-    use dotenv::var;
-    let client = PublicClient::new(&var("private_key")?, &var("public_key")?).await?;
+    let client = PublicClient::with_credentials(Credentials::from_file(".env")?).await?;
     Ok(client)
 }
 
@@ -151,3 +367,47 @@ async fn public_client() -> Result {
     env_logger::init();
     Ok(())
 }
+
+#[cfg(test)]
+#[tokio::test]
+async fn trades_snapshot_after_reply() -> Result {
+    use futures::StreamExt;
+    use serde_json::json;
+    use tokio::io::AsyncWriteExt;
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await?;
+    let url = format!("ws://{}", listener.local_addr()?);
+    spawn(async move {
+        let (stream, _) = listener.accept().await.unwrap();
+        let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+        let request = ws.next().await.unwrap().unwrap().to_string();
+        let request: serde_json::Value = serde_json::from_str(&request).unwrap();
+        let reply =
+            json!({"id": request["id"], "result": {"ch": "trades", "subscriptions": ["ETHBTC"]}});
+        let snapshot = json!({"ch": "trades", "snapshot": {"ETHBTC": [
+            {"t": 1626861109494_i64, "i": 1, "p": "0.05", "q": "1", "s": "buy"}
+        ]}});
+        // the reply and the snapshot are written at once, so the client
+        // reads the snapshot right after the reply.
+        let mut frames = vec![];
+        for msg in [reply, snapshot] {
+            let msg = msg.to_string();
+            frames.extend([0x81, 126]);
+            frames.extend((msg.len() as u16).to_be_bytes());
+            frames.extend(msg.as_bytes());
+        }
+        ws.get_mut().write_all(&frames).await.unwrap();
+        ws.next().await;
+    });
+    let client = PublicClient {
+        client: BaseClient::new(Credentials::new("secret", "key").into(), &url).await?,
+    };
+    let (mut rx, _) = client.subscribe_trades(&["ETHBTC"]).await?;
+    let trades = tokio::time::timeout(std::time::Duration::from_secs(1), rx.recv())
+        .await
+        .expect("the snapshot was dropped")
+        .unwrap();
+    assert!(trades.is_snapshot());
+    assert_eq!(trades.data().0["ETHBTC"].len(), 1);
+    Ok(())
+}
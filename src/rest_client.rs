@@ -1,16 +1,29 @@
 //! # Rest Client
-//! The Rest client exists to support two queries that are not supported in
-//! by the websocket clients. these are `symbols_info` and `get_trades`.
+//! The Rest client exists to support the queries that are not supported
+//! by the websocket clients. These are `symbols_info` and `get_trades`,
+//! and the sub-account endpoints, which require credentials.
 
-use crate::api::{RestTrades, SymbolsInfo};
+use crate::api::{
+    Account, RestTrades, SubAccount, SubAccountBalance, Symbols, SymbolsInfo, Tagged,
+};
 use crate::prelude::*;
-use reqwest::Client;
+use reqwest::{
+    header::{AUTHORIZATION, CONTENT_TYPE},
+    Client, Method,
+};
+use serde::de::DeserializeOwned;
 
+const API: &str = "https://api.exchange.cryptomkt.com";
 const SYMBOL: &str = "https://api.exchange.cryptomkt.com/api/3/public/symbol";
 const TRADES: &str = "https://api.exchange.cryptomkt.com/api/3/public/trades";
+const SUB_ACCOUNT: &str = "/api/3/sub-account";
+const SUB_ACCOUNT_TRANSFER: &str = "/api/3/sub-account/transfer";
+const SUB_ACCOUNT_BALANCE: &str = "/api/3/sub-account/balance";
+
 #[derive(Clone)]
 pub struct RestClient {
     client: Client,
+    auth: Option<Auth>,
 }
 
 impl Default for RestClient {
@@ -23,6 +36,23 @@ impl RestClient {
     pub fn new() -> RestClient {
         RestClient {
             client: Client::new(),
+            auth: None,
+        }
+    }
+
+    /// Creates a client that can query the endpoints that require authentication.
+    pub fn with_credentials(credentials: Credentials) -> RestClient {
+        RestClient {
+            client: Client::new(),
+            auth: Some(credentials.into()),
+        }
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub fn with_signer(api_key: &str, signer: impl Signer + 'static) -> RestClient {
+        RestClient {
+            client: Client::new(),
+            auth: Some(Auth::new(api_key, signer)),
         }
     }
 
@@ -31,14 +61,133 @@ impl RestClient {
         Ok(json)
     }
 
+    /// Returns the symbols and currencies of the exchange,
+    /// used to validate identifiers locally.
+    pub async fn symbols(&self) -> Result<Symbols> {
+        Ok(self.symbols_info().await?.into())
+    }
+
     pub async fn get_trades(&self) -> Result<RestTrades> {
         Ok(self.client.get(TRADES).send().await?.json().await?)
     }
+
+    /// Returns the sub-accounts of the main account.
+    pub async fn sub_accounts(&self) -> Result<Vec<SubAccount>> {
+        self.signed(Method::GET, SUB_ACCOUNT, None).await
+    }
+
+    /// Returns the wallet and spot balances of a sub-account,
+    /// tagged with the sub-account.
+    pub async fn sub_account_balance(&self, sub_account_id: &str) -> Result<SubAccountBalance> {
+        let path = format!("{}/{}", SUB_ACCOUNT_BALANCE, sub_account_id);
+        let balance: SubAccountBalance = self.signed(Method::GET, &path, None).await?;
+        let account = Account::Sub(sub_account_id.into());
+        Ok(SubAccountBalance {
+            wallet: balance.wallet.tagged(&account),
+            spot: balance.spot.tagged(&account),
+        })
+    }
+
+    /// Transfers funds from the wallet of the main account to the wallet of
+    /// a sub-account. Returns the id of the transaction.
+    pub async fn transfer_to_sub_account(
+        &self,
+        sub_account_id: &str,
+        currency: &str,
+        amount: Number,
+    ) -> Result<String> {
+        self.transfer(sub_account_id, currency, amount, "to_sub_account")
+            .await
+    }
+
+    /// Transfers funds from the wallet of a sub-account to the wallet of
+    /// the main account. Returns the id of the transaction.
+    pub async fn transfer_from_sub_account(
+        &self,
+        sub_account_id: &str,
+        currency: &str,
+        amount: Number,
+    ) -> Result<String> {
+        self.transfer(sub_account_id, currency, amount, "from_sub_account")
+            .await
+    }
+
+    async fn transfer(
+        &self,
+        sub_account_id: &str,
+        currency: &str,
+        amount: Number,
+        r#type: &str,
+    ) -> Result<String> {
+        let body = serde_json::json!({
+            "sub_account_id": sub_account_id,
+            "currency": currency,
+            "amount": amount.to_string(),
+            "type": r#type,
+        });
+        self.signed(Method::POST, SUB_ACCOUNT_TRANSFER, Some(body))
+            .await
+    }
+
+    // Sends a request authenticated with the HS256 scheme, returning the
+    // `result` field of the response.
+    async fn signed<T: DeserializeOwned>(
+        &self,
+        method: Method,
+        path: &str,
+        body: Option<serde_json::Value>,
+    ) -> Result<T> {
+        let auth = self.auth.as_ref().ok_or_else(|| {
+            Error::Credentials("the rest client was created without credentials".into())
+        })?;
+        let body = body.map(|body| body.to_string()).unwrap_or_default();
+        let time_sync = crate::time_sync();
+        let timestamp = time_sync.timestamp_millis().to_string();
+        let window = time_sync.window().map(|window| window.to_string());
+
+        let mut payload = format!("{}{}{}{}", method, path, body, timestamp);
+        if let Some(window) = &window {
+            payload += window;
+        }
+        let signature = auth.sign(payload.as_bytes()).await?;
+        let mut token = format!("{}:{}:{}", auth.api_key, signature, timestamp);
+        if let Some(window) = &window {
+            token = format!("{}:{}", token, window);
+        }
+
+        let mut request = self
+            .client
+            .request(method, format!("{}{}", API, path))
+            .header(AUTHORIZATION, format!("HS256 {}", base64::encode(token)));
+        if !body.is_empty() {
+            request = request.header(CONTENT_TYPE, "application/json").body(body);
+        }
+        let mut json: serde_json::Value = request.send().await?.json().await?;
+        if json.get("error").is_some() {
+            let error: crate::api::Error = serde_json::from_value(json)?;
+            return Err(Error::from(error));
+        }
+        Ok(serde_json::from_value(json["result"].take())?)
+    }
 }
 
+#[cfg(test)]
 #[tokio::test]
 async fn test_symbols_info() {
     let client = RestClient::new();
     let symbols_info = client.get_trades().await.unwrap();
     println!("{:?}", symbols_info);
 }
+
+#[test]
+fn deserialize_sub_account_balance() {
+    let json = r#"{
+        "wallet": [{"currency": "BTC", "available": "0.1", "reserved": "0"}],
+        "spot": [{"currency": "CLP", "available": "1000", "reserved": "10"}]
+    }"#;
+    let balance: SubAccountBalance = serde_json::from_str(json).unwrap();
+    let account = Account::Sub("178d5cf2".into());
+    let spot = balance.spot.tagged(&account);
+    assert_eq!(spot[0].account, account);
+    assert_eq!(spot[0].reserved, Number::from(10));
+}
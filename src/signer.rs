@@ -0,0 +1,110 @@
+//! # Signer
+//! Authentication requests are signed with HMAC-SHA256. By default the
+//! signature is computed in process from the private key, but a custom
+//! [`Signer`] can be used to keep the secret in a separate process.
+//!
+//! ```no_run
+//! use cryptomarket::{Signer, SignerOutput, TradingClient};
+//! use tokio::io::{AsyncReadExt, AsyncWriteExt};
+//! use tokio::net::UnixStream;
+//!
+//! /// Asks a local daemon to sign the payload.
+//! struct DaemonSigner;
+//!
+//! impl Signer for DaemonSigner {
+//!     fn sign<'a>(&'a self, payload: &'a [u8]) -> SignerOutput<'a> {
+//!         Box::pin(async move {
+//!             let mut stream = UnixStream::connect("/run/signer.sock").await?;
+//!             stream.write_all(payload).await?;
+//!             stream.shutdown().await?;
+//!             let mut signature = vec![];
+//!             stream.read_to_end(&mut signature).await?;
+//!             Ok(signature)
+//!         })
+//!     }
+//! }
+//!
+//! # async fn doc() -> Result<(), cryptomarket::Error> {
+//! let client = TradingClient::with_signer("public_key", DaemonSigner).await?;
+//! # Ok(()) }
+//! ```
+use crate::{api::Account, prelude::*};
+use std::{future::Future, pin::Pin};
+use zeroize::Zeroizing;
+
+pub type SignerOutput<'a> = Pin<Box<dyn Future<Output = Result<Vec<u8>>> + Send + 'a>>;
+
+/// Computes the HMAC-SHA256 signature of the payloads sent to the API.
+pub trait Signer: Send + Sync {
+    /// Returns the raw bytes of the signature of the payload.
+    fn sign<'a>(&'a self, payload: &'a [u8]) -> SignerOutput<'a>;
+}
+
+/// The default signer, which holds the private key in memory.
+pub struct HmacSigner {
+    secret: Zeroizing<Vec<u8>>,
+}
+
+impl HmacSigner {
+    pub fn new(private_key: &str) -> Self {
+        Self {
+            secret: Zeroizing::new(private_key.as_bytes().to_vec()),
+        }
+    }
+}
+
+impl From<&Credentials> for HmacSigner {
+    fn from(credentials: &Credentials) -> Self {
+        Self::new(credentials.private_key())
+    }
+}
+
+impl Signer for HmacSigner {
+    fn sign<'a>(&'a self, payload: &'a [u8]) -> SignerOutput<'a> {
+        let key = hmac::Key::new(hmac::HMAC_SHA256, &self.secret);
+        let signature = hmac::sign(&key, payload).as_ref().to_vec();
+        Box::pin(async move { Ok(signature) })
+    }
+}
+
+// The api key along with the signer used for its private key.
+#[derive(Clone)]
+pub(crate) struct Auth {
+    pub api_key: String,
+    pub signer: Arc<dyn Signer>,
+    pub account: Account,
+}
+
+impl Auth {
+    pub fn new(api_key: &str, signer: impl Signer + 'static) -> Self {
+        Self {
+            api_key: api_key.into(),
+            signer: Arc::new(signer),
+            account: Account::Main,
+        }
+    }
+
+    // returns the hex encoded signature of the payload.
+    pub async fn sign(&self, payload: &[u8]) -> Result<String> {
+        let signature = self.signer.sign(payload).await?;
+        Ok(hex::encode(signature))
+    }
+}
+
+impl From<Credentials> for Auth {
+    fn from(credentials: Credentials) -> Self {
+        let mut auth = Self::new(credentials.public_key(), HmacSigner::from(&credentials));
+        auth.account = credentials.account().clone();
+        auth
+    }
+}
+
+#[cfg(test)]
+#[tokio::test]
+async fn hmac_signer() {
+    let auth = Auth::from(Credentials::new("secret", "public"));
+    let signature = auth.sign(b"1626861109000").await.unwrap();
+    let key = hmac::Key::new(hmac::HMAC_SHA256, b"secret");
+    let expected = hex::encode(hmac::sign(&key, b"1626861109000"));
+    assert_eq!(signature, expected);
+}
@@ -0,0 +1,328 @@
+use crate::api::{Event, PublicTrade, TradeMap};
+use crate::prelude::*;
+use std::cmp::Ordering;
+use std::collections::HashSet;
+
+/// When the bars of a `BarBuilder` close.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BarInterval {
+    /// Bars of a fixed period of time, aligned to the unix epoch. Periods
+    /// without trades have no bar.
+    Time(chrono::Duration),
+    /// Bars that close when their volume reaches the quantity. The trade
+    /// that reaches it is not split, so bars may exceed the quantity.
+    Volume(Number),
+    /// Bars that close after a number of trades.
+    Ticks(usize),
+}
+
+/// An OHLCV bar built from public trades.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bar {
+    /// The start of the period of time bars, or the time of the first
+    /// trade of other bars.
+    pub open_time: DateTime<Utc>,
+    /// The time of the last trade of the bar.
+    pub close_time: DateTime<Utc>,
+    pub open: Number,
+    pub high: Number,
+    pub low: Number,
+    pub close: Number,
+    pub volume: Number,
+    pub volume_quote: Number,
+    pub trades: usize,
+}
+
+/// A notification of a change to the bars of a `BarBuilder`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum BarEvent {
+    /// The bar in progress of the symbol changed.
+    Updated { symbol: String, bar: Bar },
+    /// The bar of the symbol is complete. The next trade opens a new bar.
+    Closed { symbol: String, bar: Bar },
+}
+
+#[derive(Debug, Clone, Default)]
+struct SymbolBars {
+    bar: Option<Bar>,
+    last_id: Option<i64>,
+    // the open time of the last closed time bar.
+    closed: Option<DateTime<Utc>>,
+}
+
+/// Builds OHLCV bars of any interval for each symbol from the `trades`
+/// channel.
+/// ```no_run
+/// # async fn doc(client: cryptomarket::PublicClient) -> Result<(), cryptomarket::Error> {
+/// use cryptomarket::tape::{BarBuilder, BarEvent, BarInterval};
+///
+/// let interval = BarInterval::Time(chrono::Duration::seconds(5));
+/// let mut builder = BarBuilder::new(interval).expect("a valid interval");
+/// let (mut trades, _) = client.subscribe_trades(&["ETHBTC"]).await?;
+/// while let Some(trades) = trades.recv().await {
+///     for event in builder.apply(&trades) {
+///         if let BarEvent::Closed { symbol, bar } = event {
+///             println!("{}: {:?}", symbol, bar);
+///         }
+///     }
+/// }
+/// # Ok(()) }
+/// ```
+#[derive(Debug, Clone)]
+pub struct BarBuilder {
+    interval: BarInterval,
+    symbols: HashMap<String, SymbolBars>,
+}
+
+impl Bar {
+    fn new(open_time: DateTime<Utc>, trade: &PublicTrade) -> Self {
+        Bar {
+            open_time,
+            close_time: trade.timestamp,
+            open: trade.price,
+            high: trade.price,
+            low: trade.price,
+            close: trade.price,
+            volume: trade.quantity,
+            volume_quote: trade.price * trade.quantity,
+            trades: 1,
+        }
+    }
+
+    fn add(&mut self, trade: &PublicTrade) {
+        if trade.price > self.high {
+            self.high = trade.price;
+        }
+        if trade.price < self.low {
+            self.low = trade.price;
+        }
+        self.close = trade.price;
+        self.close_time = trade.timestamp;
+        self.volume += trade.quantity;
+        self.volume_quote += trade.price * trade.quantity;
+        self.trades += 1;
+    }
+}
+
+impl BarBuilder {
+    /// Returns `None` unless the interval is of at least a millisecond, a
+    /// positive volume or at least one trade.
+    pub fn new(interval: BarInterval) -> Option<Self> {
+        let valid = match interval {
+            | BarInterval::Time(period) => period.num_milliseconds() >= 1,
+            | BarInterval::Volume(volume) => {
+                volume.partial_cmp(&Number::default()) == Some(Ordering::Greater)
+            }
+            | BarInterval::Ticks(ticks) => ticks > 0,
+        };
+        valid.then(|| BarBuilder {
+            interval,
+            symbols: HashMap::new(),
+        })
+    }
+
+    pub fn interval(&self) -> BarInterval {
+        self.interval
+    }
+
+    /// The bar in progress of the symbol.
+    pub fn bar(&self, symbol: &str) -> Option<&Bar> {
+        self.symbols.get(symbol)?.bar.as_ref()
+    }
+
+    /// Adds a notification of the `trades` channel. The first snapshot of
+    /// a symbol only contains its last trades, so the bars it completes
+    /// may be missing trades. They are discarded, and only the bar in
+    /// progress is reported. Later snapshots, sent after subscribing
+    /// again, are handled like updates to fill the trades that were missed.
+    pub fn apply(&mut self, event: &Event<TradeMap>) -> Vec<BarEvent> {
+        let mut trades: Vec<_> = event.data().trades().collect();
+        trades.sort_by_key(|(_, trade)| trade.id);
+        let seeding: HashSet<&str> = trades
+            .iter()
+            .map(|(symbol, _)| *symbol)
+            .filter(|symbol| event.is_snapshot() && !self.symbols.contains_key(*symbol))
+            .collect();
+
+        let mut events = vec![];
+        for (symbol, trade) in &trades {
+            let pushed = self.push(symbol, *trade);
+            if !seeding.contains(symbol) {
+                events.extend(pushed);
+            }
+        }
+        for symbol in seeding {
+            if let Some(bar) = self.bar(symbol) {
+                events.push(BarEvent::Updated {
+                    symbol: symbol.into(),
+                    bar: *bar,
+                });
+            }
+        }
+        events
+    }
+
+    /// Adds a trade of the symbol. Trades whose id is not greater than the
+    /// id of the last trade of the symbol were already seen, and trades
+    /// before the period of the time bar in progress, or in the period of a
+    /// closed time bar, arrived too late. Both are ignored.
+    pub fn push(&mut self, symbol: &str, trade: PublicTrade) -> Vec<BarEvent> {
+        let interval = self.interval;
+        let state = match self.symbols.get_mut(symbol) {
+            | Some(state) => state,
+            | None => self.symbols.entry(symbol.into()).or_default(),
+        };
+        if matches!(state.last_id, Some(id) if id >= trade.id) {
+            return vec![];
+        }
+        state.last_id = Some(trade.id);
+
+        let mut events = vec![];
+        match (interval, &mut state.bar) {
+            | (BarInterval::Time(period), Some(bar)) => {
+                let start = period_start(trade.timestamp, period);
+                if start < bar.open_time {
+                    return events;
+                } else if start == bar.open_time {
+                    bar.add(&trade);
+                } else {
+                    events.push(BarEvent::Closed {
+                        symbol: symbol.into(),
+                        bar: *bar,
+                    });
+                    state.closed = Some(bar.open_time);
+                    *bar = Bar::new(start, &trade);
+                }
+            }
+            | (BarInterval::Time(period), None) => {
+                let start = period_start(trade.timestamp, period);
+                if matches!(state.closed, Some(closed) if start <= closed) {
+                    return events;
+                }
+                state.bar = Some(Bar::new(start, &trade));
+            }
+            | (_, Some(bar)) => bar.add(&trade),
+            | (_, None) => state.bar = Some(Bar::new(trade.timestamp, &trade)),
+        }
+
+        let bar = match state.bar {
+            | Some(bar) => bar,
+            | None => return events,
+        };
+        let complete = match interval {
+            | BarInterval::Time(_) => false,
+            | BarInterval::Volume(volume) => bar.volume >= volume,
+            | BarInterval::Ticks(ticks) => bar.trades >= ticks,
+        };
+        let symbol = symbol.into();
+        if complete {
+            state.bar = None;
+            events.push(BarEvent::Closed { symbol, bar });
+        } else {
+            events.push(BarEvent::Updated { symbol, bar });
+        }
+        events
+    }
+
+    /// Closes the time bars whose period ended before `now`, without
+    /// waiting for the next trade of their symbol.
+    pub fn close_until(&mut self, now: DateTime<Utc>) -> Vec<BarEvent> {
+        let period = match self.interval {
+            | BarInterval::Time(period) => period,
+            | _ => return vec![],
+        };
+        let mut events = vec![];
+        for (symbol, state) in &mut self.symbols {
+            if let Some(bar) = state.bar {
+                if bar.open_time + period <= now {
+                    state.bar = None;
+                    state.closed = Some(bar.open_time);
+                    events.push(BarEvent::Closed {
+                        symbol: symbol.clone(),
+                        bar,
+                    });
+                }
+            }
+        }
+        events
+    }
+}
+
+fn period_start(timestamp: DateTime<Utc>, period: chrono::Duration) -> DateTime<Utc> {
+    let offset = timestamp
+        .timestamp_millis()
+        .rem_euclid(period.num_milliseconds());
+    timestamp - chrono::Duration::milliseconds(offset)
+}
+
+#[test]
+fn build_bars() {
+    let start: DateTime<Utc> = "2021-07-21T00:00:00Z".parse().unwrap();
+    let trades = |trades: &[(i64, &str, &str)]| {
+        let trades: Vec<_> = trades
+            .iter()
+            .map(|(id, price, quantity)| {
+                serde_json::json!({
+                    "i": id,
+                    "p": price,
+                    "q": quantity,
+                    "s": "buy",
+                    "t": (start + chrono::Duration::seconds(*id)).timestamp_millis(),
+                })
+            })
+            .collect();
+        serde_json::from_value::<TradeMap>(serde_json::json!({ "ETHBTC": trades })).unwrap()
+    };
+
+    let mut builder = BarBuilder::new(BarInterval::Time(chrono::Duration::seconds(5))).unwrap();
+    // the bar completed by the snapshot is discarded.
+    let snapshot = trades(&[(3, "10", "1"), (6, "12", "1"), (7, "11", "2")]);
+    let events = builder.apply(&Event::Snapshot(snapshot));
+    assert!(matches!(&events[..], [BarEvent::Updated { bar, .. }] if bar.trades == 2));
+
+    let update = trades(&[(7, "11", "2"), (9, "9", "1"), (10, "13", "1")]);
+    let events = builder.apply(&Event::Update(update));
+    let bar = match &events[..] {
+        | [BarEvent::Updated { .. }, BarEvent::Closed { bar, .. }, BarEvent::Updated { .. }] => bar,
+        | events => panic!("unexpected events: {:?}", events),
+    };
+    assert_eq!(bar.open_time, start + chrono::Duration::seconds(5));
+    assert_eq!(bar.open, "12".parse::<Number>().unwrap());
+    assert_eq!(bar.high, "12".parse::<Number>().unwrap());
+    assert_eq!(bar.low, "9".parse::<Number>().unwrap());
+    assert_eq!(bar.close, "9".parse::<Number>().unwrap());
+    assert_eq!(bar.volume, Number::from(4));
+    assert_eq!(bar.trades, 3);
+
+    let events = builder.close_until(start + chrono::Duration::seconds(15));
+    assert!(matches!(&events[..], [BarEvent::Closed { bar, .. }] if bar.trades == 1));
+    assert!(builder.bar("ETHBTC").is_none());
+    // a late trade of the closed period does not open it again.
+    let events = builder.apply(&Event::Update(trades(&[(14, "8", "1")])));
+    assert!(events.is_empty());
+    assert!(builder.bar("ETHBTC").is_none());
+    let events = builder.apply(&Event::Update(trades(&[(15, "8", "1")])));
+    assert!(matches!(&events[..], [BarEvent::Updated { bar, .. }] if bar.trades == 1));
+
+    let mut builder = BarBuilder::new(BarInterval::Volume(Number::from(3))).unwrap();
+    let events = builder.apply(&Event::Update(trades(&[(1, "10", "2"), (2, "10", "2")])));
+    assert!(matches!(&events[1], BarEvent::Closed { bar, .. } if bar.volume == Number::from(4)));
+
+    let mut builder = BarBuilder::new(BarInterval::Ticks(2)).unwrap();
+    let events = builder.apply(&Event::Update(trades(&[(1, "10", "1"), (2, "11", "1")])));
+    assert!(matches!(
+        &events[..],
+        [BarEvent::Updated { .. }, BarEvent::Closed { .. }]
+    ));
+}
+
+#[test]
+fn invalid_intervals() {
+    let period = |millis| BarInterval::Time(chrono::Duration::milliseconds(millis));
+    assert!(BarBuilder::new(period(0)).is_none());
+    assert!(BarBuilder::new(period(-5)).is_none());
+    assert!(BarBuilder::new(period(1)).is_some());
+    assert!(BarBuilder::new(BarInterval::Volume(Number::default())).is_none());
+    assert!(BarBuilder::new(BarInterval::Volume(Number::from(-1))).is_none());
+    assert!(BarBuilder::new(BarInterval::Ticks(0)).is_none());
+}
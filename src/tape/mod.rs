@@ -0,0 +1,20 @@
+//! # Trade tapes
+//! Statistics and OHLCV bars over the public trades of the exchange,
+//! kept up to date from the `trades` channel.
+//! ```no_run
+//! # async fn doc(client: cryptomarket::PublicClient) -> Result<(), cryptomarket::Error> {
+//! use cryptomarket::tape::TradeTape;
+//!
+//! let mut tape = TradeTape::new(chrono::Duration::minutes(5));
+//! let (mut trades, _) = client.subscribe_trades(&["ETHBTC"]).await?;
+//! while let Some(trades) = trades.recv().await {
+//!     tape.extend(trades.data());
+//!     println!("{:?}", tape.stats("ETHBTC"));
+//! }
+//! # Ok(()) }
+//! ```
+pub use bars::*;
+pub use rolling::*;
+
+mod bars;
+mod rolling;
@@ -0,0 +1,167 @@
+use crate::api::{PublicTrade, TradeMap};
+use crate::prelude::*;
+use crate::Side;
+use std::collections::VecDeque;
+
+/// The trades of each symbol within a rolling window of time, ending at
+/// the last trade of the symbol. Symbols that stop trading keep their last
+/// window until `evict_until` is called.
+#[derive(Debug, Clone)]
+pub struct TradeTape {
+    window: chrono::Duration,
+    trades: HashMap<String, VecDeque<PublicTrade>>,
+}
+
+/// Statistics of the trades of a symbol in the window of a `TradeTape`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TradeStats {
+    pub count: usize,
+    /// The quantity bought by takers.
+    pub buy_volume: Number,
+    /// The quantity sold by takers.
+    pub sell_volume: Number,
+    /// The volume weighted average price.
+    pub vwap: Number,
+    pub last_price: Number,
+    pub last_timestamp: DateTime<Utc>,
+}
+
+impl TradeTape {
+    pub fn new(window: chrono::Duration) -> Self {
+        TradeTape {
+            window,
+            trades: HashMap::new(),
+        }
+    }
+
+    pub fn window(&self) -> chrono::Duration {
+        self.window
+    }
+
+    /// Adds a trade of the symbol. Trades whose id is not greater than the
+    /// id of the last trade of the symbol were already seen, and are
+    /// ignored. Returns whether the trade was added.
+    pub fn push(&mut self, symbol: &str, trade: PublicTrade) -> bool {
+        let trades = match self.trades.get_mut(symbol) {
+            | Some(trades) => trades,
+            | None => self.trades.entry(symbol.into()).or_default(),
+        };
+        if matches!(trades.back(), Some(last) if last.id >= trade.id) {
+            return false;
+        }
+        trades.push_back(trade);
+        evict(trades, trade.timestamp - self.window);
+        true
+    }
+
+    /// Adds the trades of a notification of the `trades` channel, either
+    /// a snapshot or an update.
+    pub fn extend(&mut self, trades: &TradeMap) {
+        let mut trades: Vec<_> = trades.trades().collect();
+        trades.sort_by_key(|(_, trade)| trade.id);
+        for (symbol, trade) in trades {
+            self.push(symbol, trade);
+        }
+    }
+
+    /// Removes the trades that are out of the window ending at `now`, and
+    /// the symbols left without trades. Calling it periodically keeps the
+    /// stats of symbols that stopped trading from going stale.
+    pub fn evict_until(&mut self, now: DateTime<Utc>) {
+        let start = now - self.window;
+        self.trades.retain(|_, trades| {
+            evict(trades, start);
+            !trades.is_empty()
+        });
+    }
+
+    /// The trades of the symbol in the window, oldest first.
+    pub fn trades(&self, symbol: &str) -> impl Iterator<Item = &PublicTrade> + '_ {
+        self.trades.get(symbol).into_iter().flatten()
+    }
+
+    pub fn symbols(&self) -> impl Iterator<Item = &str> + '_ {
+        self.trades.keys().map(String::as_str)
+    }
+
+    /// The statistics of the trades of the symbol in the window, or `None`
+    /// if no trade of the symbol was received.
+    pub fn stats(&self, symbol: &str) -> Option<TradeStats> {
+        let trades = self.trades.get(symbol)?;
+        let last = trades.back()?;
+        let zero = Number::default();
+        let (mut buy_volume, mut sell_volume, mut notional) = (zero, zero, zero);
+        for trade in trades {
+            match trade.side {
+                | Side::Buy => buy_volume += trade.quantity,
+                | Side::Sell => sell_volume += trade.quantity,
+            }
+            notional += trade.price * trade.quantity;
+        }
+        let volume = buy_volume + sell_volume;
+        Some(TradeStats {
+            count: trades.len(),
+            buy_volume,
+            sell_volume,
+            vwap: if volume > zero {
+                notional / volume
+            } else {
+                last.price
+            },
+            last_price: last.price,
+            last_timestamp: last.timestamp,
+        })
+    }
+}
+
+// Removes the trades at or before the start of the window.
+fn evict(trades: &mut VecDeque<PublicTrade>, start: DateTime<Utc>) {
+    while matches!(trades.front(), Some(first) if first.timestamp <= start) {
+        trades.pop_front();
+    }
+}
+
+impl TradeStats {
+    /// The quantity traded.
+    pub fn volume(&self) -> Number {
+        self.buy_volume + self.sell_volume
+    }
+}
+
+#[test]
+fn rolling_stats() {
+    let n = |n: &str| n.parse::<Number>().unwrap();
+    let start: DateTime<Utc> = "2021-07-21T00:00:00Z".parse().unwrap();
+    let trade = |id: i64, price: &str, quantity: &str, side: Side| PublicTrade {
+        id,
+        price: n(price),
+        quantity: n(quantity),
+        side,
+        timestamp: start + chrono::Duration::seconds(id),
+    };
+    let mut tape = TradeTape::new(chrono::Duration::seconds(10));
+    assert!(tape.push("ETHBTC", trade(1, "10", "1", Side::Buy)));
+    assert!(tape.push("ETHBTC", trade(5, "20", "3", Side::Sell)));
+    assert!(!tape.push("ETHBTC", trade(5, "20", "3", Side::Sell)));
+    let stats = tape.stats("ETHBTC").unwrap();
+    assert_eq!(stats.count, 2);
+    assert_eq!(stats.buy_volume, n("1"));
+    assert_eq!(stats.sell_volume, n("3"));
+    assert_eq!(stats.vwap, n("17.5"));
+    assert_eq!(stats.last_price, n("20"));
+
+    // the first trade leaves the window.
+    tape.push("ETHBTC", trade(11, "30", "1", Side::Buy));
+    let stats = tape.stats("ETHBTC").unwrap();
+    assert_eq!(stats.count, 2);
+    assert_eq!(stats.volume(), n("4"));
+    assert_eq!(stats.vwap, n("22.5"));
+    assert!(tape.stats("BTCUSDT").is_none());
+
+    // no more trades arrive, and the window moves on without them.
+    tape.evict_until(start + chrono::Duration::seconds(16));
+    assert_eq!(tape.stats("ETHBTC").unwrap().count, 1);
+    tape.evict_until(start + chrono::Duration::seconds(21));
+    assert!(tape.stats("ETHBTC").is_none());
+    assert_eq!(tape.symbols().count(), 0);
+}
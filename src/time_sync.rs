@@ -0,0 +1,143 @@
+//! # Time synchronization
+//! Login requests are signed with the current timestamp, so they are
+//! rejected if the local clock drifts away from the exchange's clock.
+//! The offset between both clocks is estimated from the timestamps sent
+//! by the exchange, and it is applied to every login request.
+//!
+//! The offset is shared by all clients in the process, since it is a
+//! property of the host clock.
+//! ```no_run
+//! # async fn doc() {
+//! let time_sync = cryptomarket::time_sync();
+//! // allow the exchange to accept login requests up to 20 seconds old.
+//! time_sync.set_window(Some(20_000));
+//! let mut skew = time_sync.watch();
+//! while skew.changed().await.is_ok() {
+//!     println!("clock offset: {}ms", skew.borrow().num_milliseconds());
+//! }
+//! # }
+//! ```
+use crate::prelude::*;
+use std::collections::VecDeque;
+use std::sync::{
+    atomic::{AtomicI64, AtomicU32, Ordering::Relaxed},
+    Mutex, OnceLock,
+};
+use tokio::sync::watch;
+
+// The number of recent samples used to estimate the offset.
+const MAX_SAMPLES: usize = 64;
+// Offsets larger than this, in milliseconds, are logged as warnings.
+const SKEW_WARNING: i64 = 1000;
+
+static TIME_SYNC: OnceLock<TimeSync> = OnceLock::new();
+
+/// Returns the time synchronization state of the process.
+pub fn time_sync() -> &'static TimeSync {
+    TIME_SYNC.get_or_init(TimeSync::new)
+}
+
+/// Estimates the offset of the exchange's clock relative to the local clock.
+///
+/// Each sample is the difference between a server timestamp and the
+/// local time at which it was received. Since a message can't arrive
+/// before it was sent, samples underestimate the offset by the network
+/// latency, so the estimate is the largest of the recent samples.
+pub struct TimeSync {
+    samples: Mutex<VecDeque<i64>>,
+    offset: AtomicI64,
+    window: AtomicU32,
+    tx: watch::Sender<chrono::Duration>,
+}
+
+impl TimeSync {
+    fn new() -> Self {
+        Self {
+            samples: Mutex::default(),
+            offset: AtomicI64::new(0),
+            window: AtomicU32::new(0),
+            tx: watch::channel(chrono::Duration::zero()).0,
+        }
+    }
+
+    /// The estimated offset of the exchange's clock. A positive
+    /// offset means the local clock is behind.
+    pub fn offset(&self) -> chrono::Duration {
+        chrono::Duration::milliseconds(self.offset.load(Relaxed))
+    }
+
+    /// The current time according to the exchange's clock.
+    pub fn now(&self) -> DateTime<Utc> {
+        Utc::now() + self.offset()
+    }
+
+    /// Records a timestamp sent by the exchange.
+    pub fn observe(&self, server_time: DateTime<Utc>) {
+        self.observe_millis(server_time.timestamp_millis());
+    }
+
+    pub(crate) fn observe_millis(&self, server_time: i64) {
+        let sample = server_time - Utc::now().timestamp_millis();
+        let offset = {
+            let mut samples = self.samples.lock().unwrap_or_else(|err| err.into_inner());
+            if samples.len() == MAX_SAMPLES {
+                samples.pop_front();
+            }
+            samples.push_back(sample);
+            samples.iter().copied().max().unwrap_or_default()
+        };
+        let previous = self.offset.swap(offset, Relaxed);
+        if previous != offset {
+            if offset.abs() > SKEW_WARNING && previous.abs() <= SKEW_WARNING {
+                warn!("local clock is off by {}ms from cryptomkt's clock.", offset);
+            }
+            self.tx.send_replace(chrono::Duration::milliseconds(offset));
+        }
+    }
+
+    /// Discards all samples, setting the offset back to zero.
+    pub fn reset(&self) {
+        self.samples
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .clear();
+        self.offset.store(0, Relaxed);
+        self.tx.send_replace(chrono::Duration::zero());
+    }
+
+    /// The validity window of login requests, in milliseconds.
+    pub fn window(&self) -> Option<u32> {
+        Some(self.window.load(Relaxed)).filter(|window| *window != 0)
+    }
+
+    /// Sets the `window` parameter of login requests, in milliseconds. The
+    /// exchange rejects signatures older than this window, which defaults
+    /// to 10 seconds and can be at most 60 seconds.
+    pub fn set_window(&self, window: Option<u32>) {
+        self.window.store(window.unwrap_or_default(), Relaxed);
+    }
+
+    /// Returns a receiver notified whenever the estimated offset changes.
+    pub fn watch(&self) -> watch::Receiver<chrono::Duration> {
+        self.tx.subscribe()
+    }
+
+    // returns the timestamp to sign login requests with, in milliseconds.
+    pub(crate) fn timestamp_millis(&self) -> i64 {
+        self.now().timestamp_millis()
+    }
+}
+
+#[test]
+fn estimate_offset() {
+    let time_sync = TimeSync::new();
+    let now = Utc::now().timestamp_millis();
+    time_sync.observe_millis(now + 4000);
+    time_sync.observe_millis(now + 5000);
+    time_sync.observe_millis(now + 3000);
+    let offset = time_sync.offset().num_milliseconds();
+    assert!((4900..=5000).contains(&offset));
+    assert_eq!(*time_sync.watch().borrow(), time_sync.offset());
+    time_sync.reset();
+    assert_eq!(time_sync.offset(), chrono::Duration::zero());
+}
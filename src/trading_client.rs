@@ -1,4 +1,4 @@
-use crate::api::{Balance, Fee, Order};
+use crate::api::{Account, Balance, Fee, Order, Tagged};
 use crate::prelude::*;
 
 use crate::NewOrder;
@@ -12,22 +12,69 @@ pub struct TradingClient {
 
 impl TradingClient {
     pub async fn new(priv_key: &str, pub_key: &str) -> Result<Self> {
-        let client = BaseClient::new(priv_key, pub_key, WEBSOCKET_URL).await?;
+        Self::with_credentials(Credentials::new(priv_key, pub_key)).await
+    }
+
+    pub async fn with_credentials(credentials: Credentials) -> Result<Self> {
+        Self::with_auth(credentials.into()).await
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub async fn with_signer(api_key: &str, signer: impl Signer + 'static) -> Result<Self> {
+        Self::with_auth(Auth::new(api_key, signer)).await
+    }
+
+    pub(crate) async fn with_auth(auth: Auth) -> Result<Self> {
+        let client = BaseClient::new(auth, WEBSOCKET_URL).await?;
         client.authenticate().await?;
         Ok(Self { client })
     }
+    /// The account the client is authenticated as.
+    pub fn account(&self) -> &Account {
+        self.client.account()
+    }
+
     async fn _is_auth(&self) -> bool {
         self.client.is_auth().await
     }
 
+    /// Sends a request for any method of the API, including the ones
+    /// not yet wrapped by this client. Returns the `result` field of the
+    /// response.
+    /// ```no_run
+    /// # async fn doc(client: cryptomarket::TradingClient) -> Result<(), cryptomarket::Error> {
+    /// let result = client.call("spot_get_orders", serde_json::json!({})).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        self.client.call(method, params).await
+    }
+
+    /// Subscribes to any channel of the API with the params given.
+    /// Returns the notifications as raw json, along with the result of
+    /// the subscription request.
+    pub async fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Receiver<serde_json::Value>, serde_json::Value)> {
+        self.client.subscribe_raw(channel, params).await
+    }
+
     pub async fn subscribe_orders(&self) -> Result<Receiver<Order>> {
         let (tx, rx) = channel(8);
         let request = Request::spot_subscribe();
+        let account = self.account().clone();
         self.client
-            .subscribe("spot_order", tx.clone(), |res| res.as_spot_order())
+            .subscribe("spot_order", tx.clone(), move |res| {
+                res.as_spot_order().map(|order| order.tagged(&account))
+            })
             .await;
+        let account = self.account().clone();
         self.client
-            .subscribe_vec("spot_orders", tx, |res| res.as_spot_orders())
+            .subscribe_vec("spot_orders", tx, move |res| {
+                res.as_spot_orders().map(|orders| orders.tagged(&account))
+            })
             .await;
         self.client.request(&request).await?;
 
@@ -46,91 +93,80 @@ impl TradingClient {
     pub async fn get_orders(&self) -> Result<Vec<Order>> {
         let request = Request::spot_get_orders();
         self.client
-            .request(&request)
-            .await?
-            .as_spot_orders()
-            .map_err(Error::unexpected)
+            .request_as(&request, Response::as_spot_orders)
+            .await
+            .map(|order| order.tagged(self.account()))
     }
     /// places a new order on the exchange.
     pub async fn place_order(&self, order: NewOrder) -> Result<Order> {
         let request = Request::spot_new_order(order);
         self.client
-            .request(&request)
-            .await?
-            .as_spot_order()
-            .map_err(Error::unexpected)
+            .request_as(&request, Response::as_spot_order)
+            .await
+            .map(|order| order.tagged(self.account()))
     }
     /// cancels all orders issed by the client.
     pub async fn cancel_orders(&self) -> Result<Vec<Order>> {
         let request = Request::spot_cancel_orders();
         self.client
-            .request(&request)
-            .await?
-            .as_spot_orders()
-            .map_err(Error::unexpected)
+            .request_as(&request, Response::as_spot_orders)
+            .await
+            .map(|order| order.tagged(self.account()))
     }
     /// cancels a specific order using the `client_order_id`.
     pub async fn cancel_order(&self, client_order_id: &str) -> Result<Order> {
         let request = Request::spot_cancel_order(client_order_id);
         self.client
-            .request(&request)
-            .await?
-            .as_spot_order()
-            .map_err(Error::unexpected)
+            .request_as(&request, Response::as_spot_order)
+            .await
+            .map(|order| order.tagged(self.account()))
     }
 
     pub async fn get_balance(&self) -> Result<Vec<Balance>> {
         let request = Request::spot_balances();
         self.client
-            .request(&request)
-            .await?
-            .as_balances()
-            .or_else(|e| {
-                let orders = e.as_spot_orders().map_err(Error::unexpected)?;
-                if orders.is_empty() {
-                    Ok(vec![])
-                } else {
-                    Err(Error::UnexpectedResponse)
-                }
+            .request_as(&request, |res| match res.as_balances() {
+                | Err(res) if res.is_empty_list() => Ok(vec![]),
+                | balances => balances,
             })
+            .await
+            .map(|balances| balances.tagged(self.account()))
     }
     /// get the fees for all markets.
     pub async fn get_fees(&self) -> Result<Vec<Fee>> {
         let request = Request::spot_fees();
         self.client
-            .request(&request)
-            .await?
-            .as_spot_fees()
-            .map_err(Error::unexpected)
+            .request_as(&request, Response::as_spot_fees)
+            .await
     }
     /// get the fee for a specific market
     /// ```
     /// # let client = TradingClient::new("", "").await?;
     /// let fee = client.get_fee("BTCCLP").await?;
     /// ```
-    pub async fn get_fee(&self, symbol: &str) -> Result<Fee> {
-        let request = Request::spot_fee(symbol);
+    pub async fn get_fee(&self, symbol: impl AsRef<str>) -> Result<Fee> {
+        let request = Request::spot_fee(symbol.as_ref());
         self.client
-            .request(&request)
-            .await?
-            .as_spot_fee()
-            .map_err(Error::unexpected)
+            .request_as(&request, Response::as_spot_fee)
+            .await
     }
 
-    pub async fn replace_order(&self, order_id: &str, quantity: f64, price: f64) -> Result<Order> {
+    pub async fn replace_order(
+        &self,
+        order_id: &str,
+        quantity: Number,
+        price: Number,
+    ) -> Result<Order> {
         let request = Request::spot_replace_order(order_id, quantity, price);
         self.client
-            .request(&request)
-            .await?
-            .as_spot_order()
-            .map_err(Error::unexpected)
+            .request_as(&request, Response::as_spot_order)
+            .await
+            .map(|order| order.tagged(self.account()))
     }
 }
 
 async fn _auth_client() -> Result<TradingClient> {
-    dotenv::dotenv()?;
-    use dotenv::var;
-    let client = TradingClient::new(&var("private_key")?, &var("public_key")?).await?;
+    let client = TradingClient::with_credentials(Credentials::from_file(".env")?).await?;
     Ok(client)
 }
 #[cfg(test)]
@@ -148,9 +184,9 @@ async fn test() -> Result {
     // println!("Cancelling all orders response: {:?}", response);
 
     let order = crate::api::NewOrderBuilder::default()
-        .symbol("BTCUSDT".into())
-        .price(61640.13)
-        .quantity(0.00111)
+        .symbol("BTCUSDT")
+        .price("61640.13".parse().unwrap())
+        .quantity("0.00111".parse().unwrap())
         .side(crate::api::Side::Buy)
         .build()
         .unwrap();
@@ -2,6 +2,25 @@ use super::api::*;
 use super::*;
 use crate::api::Subscriptions as Subs;
 
+// The client a method of the API is sent to.
+pub(crate) enum Endpoint {
+    Public,
+    Trading,
+    Wallet,
+}
+
+impl Endpoint {
+    pub(crate) fn of(method: &str) -> Self {
+        if method.starts_with("spot_") {
+            Endpoint::Trading
+        } else if method.contains("wallet_balance") || method.contains("transactions") {
+            Endpoint::Wallet
+        } else {
+            Endpoint::Public
+        }
+    }
+}
+
 /// An interface over `TradingClient`, `WalletClient` and `PublicClient`.
 #[derive(Clone)]
 pub struct TripleClient {
@@ -12,9 +31,22 @@ pub struct TripleClient {
 
 impl TripleClient {
     pub async fn new(private_key: &str, public_key: &str) -> Result<Self> {
-        let trading = TradingClient::new(private_key, public_key).await?;
-        let wallet = WalletClient::new(private_key, public_key).await?;
-        let public = PublicClient::new(private_key, public_key).await?;
+        Self::with_credentials(Credentials::new(private_key, public_key)).await
+    }
+
+    pub async fn with_credentials(credentials: Credentials) -> Result<Self> {
+        Self::with_auth(credentials.into()).await
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub async fn with_signer(api_key: &str, signer: impl Signer + 'static) -> Result<Self> {
+        Self::with_auth(Auth::new(api_key, signer)).await
+    }
+
+    async fn with_auth(auth: Auth) -> Result<Self> {
+        let trading = TradingClient::with_auth(auth.clone()).await?;
+        let wallet = WalletClient::with_auth(auth.clone()).await?;
+        let public = PublicClient::with_auth(auth).await?;
         Ok(Self {
             trading,
             wallet,
@@ -22,44 +54,111 @@ impl TripleClient {
         })
     }
 
-    pub async fn subscriptions(&self, channel: &str) -> Result<api::Response> {
+    /// Sends a request for any method of the API, including the ones
+    /// not yet wrapped by this client. Returns the `result` field of the
+    /// response. The request is sent to the trading client for `spot_`
+    /// methods, to the wallet client for wallet balance and transaction
+    /// methods, and to the public client otherwise.
+    /// ```no_run
+    /// # async fn doc(client: cryptomarket::TripleClient) -> Result<(), cryptomarket::Error> {
+    /// let result = client.call("spot_get_orders", serde_json::json!({})).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        match Endpoint::of(method) {
+            | Endpoint::Trading => self.trading.call(method, params).await,
+            | Endpoint::Wallet => self.wallet.call(method, params).await,
+            | Endpoint::Public => self.public.call(method, params).await,
+        }
+    }
+
+    /// Subscribes to any channel of the API with the params given.
+    /// Returns the notifications as raw json, along with the result of
+    /// the subscription request.
+    pub async fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Receiver<serde_json::Value>, serde_json::Value)> {
+        self.public.subscribe_raw(channel, params).await
+    }
+
+    pub async fn subscriptions(&self, channel: Channel) -> Result<api::Response> {
         self.public.subscriptions(channel).await
     }
-    pub async fn subscribe_trades(&self, sym: &[&str]) -> Result<(Receiver<TradeMap>, Subs)> {
+    pub async fn subscribe_trades(
+        &self,
+        sym: &[impl AsRef<str>],
+    ) -> Result<(Receiver<Event<TradeMap>>, Subs)> {
         self.public.subscribe_trades(sym).await
     }
 
     pub async fn subscribe_full_orderbook(
         &self,
-        symbols: &[&str],
-    ) -> Result<(Receiver<HashMap<String, Orderbook>>, Subs)> {
+        symbols: &[impl AsRef<str>],
+    ) -> Result<(Receiver<Event<OrderbookMap>>, Subs)> {
         self.public.subscribe_full_orderbook(symbols).await
     }
     pub async fn subscribe_ticker(
         &self,
-        symbols: &[&str],
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
     ) -> Result<(Receiver<Ticker>, Subs)> {
         self.public.subscribe_ticker(symbols, speed).await
     }
     pub async fn subscribe_top_orderbook(
         &self,
-        symbols: &[&str],
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        speed: OrderbookSpeed,
     ) -> Result<(Receiver<TopOrderMap>, Subs)> {
         self.public.subscribe_top_order(symbols, speed).await
     }
     pub async fn subscribe_partial_orderbook(
         &self,
-        symbols: &[&str],
-        depth: &str,
-        speed: &str,
+        symbols: &[impl AsRef<str>],
+        depth: OrderbookDepth,
+        speed: OrderbookSpeed,
     ) -> Result<(Receiver<HashMap<String, Orderbook>>, Subs)> {
         self.public
             .subscribe_partial_orderbook(symbols, depth, speed)
             .await
     }
 
+    pub async fn subscribe_price_ticker(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<PriceTickerMap>, Subs)> {
+        self.public.subscribe_price_ticker(symbols, speed).await
+    }
+
+    pub async fn subscribe_ticker_batch(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<Ticker>, Subs)> {
+        self.public.subscribe_ticker_batch(symbols, speed).await
+    }
+
+    pub async fn subscribe_price_ticker_batch(
+        &self,
+        symbols: &[impl AsRef<str>],
+        speed: TickerSpeed,
+    ) -> Result<(Receiver<PriceTickerMap>, Subs)> {
+        self.public
+            .subscribe_price_ticker_batch(symbols, speed)
+            .await
+    }
+
+    pub async fn subscribe_candles(
+        &self,
+        symbols: &[impl AsRef<str>],
+        period: CandlePeriod,
+        limit: i32,
+    ) -> Result<(Receiver<Event<CandleMap>>, Subs)> {
+        self.public.subscribe_candles(symbols, period, limit).await
+    }
+
     pub async fn subscribe_orders(&self) -> Result<Receiver<Order>> {
         self.trading.subscribe_orders().await
     }
@@ -97,7 +196,7 @@ impl TripleClient {
     /// # let client = TripleClient::new("", "").await?;
     /// let fee = client.get_fee("BTCCLP").await?;
     /// ```
-    pub async fn get_fee(&self, symbol: &str) -> Result<Fee> {
+    pub async fn get_fee(&self, symbol: impl AsRef<str>) -> Result<Fee> {
         self.trading.get_fee(symbol).await
     }
 
@@ -105,7 +204,7 @@ impl TripleClient {
     /// ```no_run
     /// let balance = client.currency_balance("BTC").await?;
     /// ```
-    pub async fn currency_balance(&self, currency: &str) -> Result<Amount> {
+    pub async fn currency_balance(&self, currency: impl AsRef<str>) -> Result<Amount> {
         self.wallet.currency_balance(currency).await
     }
     /// Used to get the current wallet balance for all currencies.
@@ -137,7 +236,12 @@ impl TripleClient {
         self.wallet.subscribe_transactions().await
     }
 
-    pub async fn replace_order(&self, order_id: &str, quantity: f64, price: f64) -> Result<Order> {
+    pub async fn replace_order(
+        &self,
+        order_id: &str,
+        quantity: Number,
+        price: Number,
+    ) -> Result<Order> {
         self.trading.replace_order(order_id, quantity, price).await
     }
 
@@ -153,3 +257,14 @@ impl TripleClient {
         self.wallet.unsubscribe_wallet_balances().await
     }
 }
+
+#[test]
+fn endpoints() {
+    assert!(matches!(Endpoint::of("spot_get_orders"), Endpoint::Trading));
+    assert!(matches!(Endpoint::of("wallet_balances"), Endpoint::Wallet));
+    assert!(matches!(
+        Endpoint::of("subscribe_transactions"),
+        Endpoint::Wallet
+    ));
+    assert!(matches!(Endpoint::of("subscriptions"), Endpoint::Public));
+}
@@ -1,4 +1,4 @@
-use crate::api::Balance;
+use crate::api::{Account, Balance, Tagged};
 use crate::{
     api::{Amount, Request, Transaction},
     base_client::BaseClient,
@@ -17,23 +17,62 @@ pub struct WalletClient {
 
 impl WalletClient {
     pub async fn new(priv_key: &str, pub_key: &str) -> Result<Self> {
-        let client = BaseClient::new(priv_key, pub_key, WEBSOCKET_URL).await?;
+        Self::with_credentials(Credentials::new(priv_key, pub_key)).await
+    }
+
+    pub async fn with_credentials(credentials: Credentials) -> Result<Self> {
+        Self::with_auth(credentials.into()).await
+    }
+
+    /// Creates a client whose requests are signed by a custom signer.
+    pub async fn with_signer(api_key: &str, signer: impl Signer + 'static) -> Result<Self> {
+        Self::with_auth(Auth::new(api_key, signer)).await
+    }
+
+    pub(crate) async fn with_auth(auth: Auth) -> Result<Self> {
+        let client = BaseClient::new(auth, WEBSOCKET_URL).await?;
         client.authenticate().await?;
         Ok(Self { client })
     }
+    /// The account the client is authenticated as.
+    pub fn account(&self) -> &Account {
+        self.client.account()
+    }
+
     pub async fn is_auth(&self) -> bool {
         self.client.is_auth().await
     }
 
+    /// Sends a request for any method of the API, including the ones
+    /// not yet wrapped by this client. Returns the `result` field of the
+    /// response.
+    /// ```no_run
+    /// # async fn doc(client: cryptomarket::WalletClient) -> Result<(), cryptomarket::Error> {
+    /// let result = client.call("wallet_balances", serde_json::json!({})).await?;
+    /// # Ok(()) }
+    /// ```
+    pub async fn call(&self, method: &str, params: serde_json::Value) -> Result<serde_json::Value> {
+        self.client.call(method, params).await
+    }
+
+    /// Subscribes to any channel of the API with the params given.
+    /// Returns the notifications as raw json, along with the result of
+    /// the subscription request.
+    pub async fn subscribe_raw(
+        &self,
+        channel: &str,
+        params: serde_json::Value,
+    ) -> Result<(Receiver<serde_json::Value>, serde_json::Value)> {
+        self.client.subscribe_raw(channel, params).await
+    }
+
     /// Returns the available balance for the specific currency
     /// ```no_run
     /// let balance = client.currency_balance("BTC").await?;
     /// ```
-    pub async fn currency_balance(&self, currency: &str) -> Result<Amount> {
-        let request = Request::wallet_balance(currency);
-        let amount = self.client.request(&request).await?;
-
-        amount.as_amount().map_err(Error::unexpected)
+    pub async fn currency_balance(&self, currency: impl AsRef<str>) -> Result<Amount> {
+        let request = Request::wallet_balance(currency.as_ref());
+        self.client.request_as(&request, Response::as_amount).await
     }
     /// Used to get the current wallet balance for all currencies.
     /// ```no_run
@@ -47,8 +86,10 @@ impl WalletClient {
     /// ```
     pub async fn wallet_balance(&self) -> Result<Vec<Balance>> {
         let request = Request::wallet_balances();
-        let balance = self.client.request(&request).await?;
-        balance.as_balances().map_err(Error::unexpected)
+        self.client
+            .request_as(&request, Response::as_balances)
+            .await
+            .map(|balances| balances.tagged(self.account()))
     }
     /// Used to subscribe to changes to the wallet balance for all currencies.
     /// ```no_run
@@ -60,11 +101,17 @@ impl WalletClient {
         let (tx, rx) = mpsc::channel(1);
         let success = self.client.request(&request).await?.success();
         if success {
+            let account = self.account().clone();
             self.client
-                .subscribe_vec("wallet_balances", tx.clone(), |res| res.as_balances())
+                .subscribe_vec("wallet_balances", tx.clone(), move |res| {
+                    res.as_balances().map(|balances| balances.tagged(&account))
+                })
                 .await;
+            let account = self.account().clone();
             self.client
-                .subscribe("wallet_balance_update", tx, |res| res.as_balance())
+                .subscribe("wallet_balance_update", tx, move |res| {
+                    res.as_balance().map(|balance| balance.tagged(&account))
+                })
                 .await;
         }
         Ok(rx)
@@ -101,10 +148,7 @@ impl WalletClient {
 }
 
 async fn _auth_client() -> Result<WalletClient> {
-    dotenv::dotenv()?;
-    use dotenv::var;
-
-    let client = WalletClient::new(&var("private_key")?, &var("public_key")?).await?;
+    let client = WalletClient::with_credentials(Credentials::from_file(".env")?).await?;
 
     Ok(client)
 }